@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::BPS_DENOMINATOR;
+use crate::error::ErrorCode;
+
+/// LP tokens for the first deposit: sqrt(base * quote), as in a Uniswap v2 pair.
+pub fn initial_lp_amount(base_amount: u64, quote_amount: u64) -> Result<u64> {
+    let product = (base_amount as u128)
+        .checked_mul(quote_amount as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let lp_amount = product.isqrt();
+    require!(lp_amount > 0, ErrorCode::InsufficientLiquidity);
+    Ok(u64::try_from(lp_amount).map_err(|_| ErrorCode::MathOverflow)?)
+}
+
+/// Constant-product output for `amount_in`, with `fee_bps` kept in the pool.
+pub fn swap_output(amount_in: u64, reserve_in: u64, reserve_out: u64, fee_bps: u16) -> Result<u64> {
+    require!(reserve_in > 0 && reserve_out > 0, ErrorCode::InsufficientLiquidity);
+
+    let amount_in_after_fee = (amount_in as u128)
+        .checked_mul(BPS_DENOMINATOR as u128 - fee_bps as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let numerator = amount_in_after_fee
+        .checked_mul(reserve_out as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let denominator = (reserve_in as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_add(amount_in_after_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(u64::try_from(numerator / denominator).map_err(|_| ErrorCode::MathOverflow)?)
+}
+
+/// Share of both reserves redeemed by burning `lp_amount` out of `lp_supply`.
+pub fn redeem_amounts(
+    lp_amount: u64,
+    lp_supply: u64,
+    base_reserve: u64,
+    quote_reserve: u64,
+) -> Result<(u64, u64)> {
+    require!(lp_amount > 0 && lp_amount <= lp_supply, ErrorCode::InsufficientLiquidity);
+    let share = |reserve: u64| -> Result<u64> {
+        let amount = (reserve as u128)
+            .checked_mul(lp_amount as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            / lp_supply as u128;
+        Ok(u64::try_from(amount).map_err(|_| ErrorCode::MathOverflow)?)
+    };
+    Ok((share(base_reserve)?, share(quote_reserve)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initial_lp_amount() {
+        assert_eq!(initial_lp_amount(1_000_000, 4_000_000).unwrap(), 2_000_000);
+        assert_eq!(initial_lp_amount(u64::MAX, u64::MAX).unwrap(), u64::MAX);
+        assert!(initial_lp_amount(0, 1_000).is_err());
+    }
+
+    #[test]
+    fn test_swap_output_keeps_invariant() {
+        let (reserve_in, reserve_out) = (1_000_000u64, 2_000_000u64);
+        let out = swap_output(10_000, reserve_in, reserve_out, 30).unwrap();
+        // 10_000 * 0.997 * 2_000_000 / (1_000_000 + 9_970)
+        assert_eq!(out, 19_743);
+
+        let k_before = reserve_in as u128 * reserve_out as u128;
+        let k_after = (reserve_in + 10_000) as u128 * (reserve_out - out) as u128;
+        assert!(k_after >= k_before);
+
+        assert_eq!(swap_output(10_000, reserve_in, reserve_out, 0).unwrap(), 19_801);
+        assert!(swap_output(10_000, 0, reserve_out, 30).is_err());
+    }
+
+    #[test]
+    fn test_redeem_amounts() {
+        assert_eq!(redeem_amounts(2_000, 2_000, 1_000, 4_000).unwrap(), (1_000, 4_000));
+        assert_eq!(redeem_amounts(500, 2_000, 1_000, 4_001).unwrap(), (250, 1_000));
+        assert!(redeem_amounts(0, 2_000, 1_000, 4_000).is_err());
+        assert!(redeem_amounts(2_001, 2_000, 1_000, 4_000).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::error::ErrorCode;
+
+pub use anchor_lang::solana_program::sysvar::instructions::ID as INSTRUCTIONS_SYSVAR_ID;
+
+pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+const SIGNATURE_SIZE: usize = 64;
+
+/// Message the attester signs to close the private round:
+/// kickstarter || final_commitments_root || attested_total_amount (le).
+pub fn attestation_message(
+    kickstarter: &Pubkey,
+    final_commitments_root: &[u8; 32],
+    attested_total_amount: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(72);
+    message.extend_from_slice(kickstarter.as_ref());
+    message.extend_from_slice(final_commitments_root);
+    message.extend_from_slice(&attested_total_amount.to_le_bytes());
+    message
+}
+
+/// Checks that the instruction right before the current one is an Ed25519
+/// sigverify of `message` by `attester` with exactly `signature`. The precompile
+/// has already rejected the transaction if the signature itself is invalid.
+pub fn verify_ed25519_attestation(
+    instructions_sysvar: &AccountInfo,
+    attester: &Pubkey,
+    message: &[u8],
+    signature: &[u8; 64],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, ErrorCode::InvalidAttestation);
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+
+    require!(ix.program_id == ED25519_PROGRAM_ID, ErrorCode::InvalidAttestation);
+    require!(ix.accounts.is_empty(), ErrorCode::InvalidAttestation);
+
+    let data = &ix.data;
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE && data[0] == 1,
+        ErrorCode::InvalidAttestation
+    );
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = SIGNATURE_OFFSETS_START;
+    let signature_offset = read_u16(offsets) as usize;
+    let signature_ix_index = read_u16(offsets + 2);
+    let pubkey_offset = read_u16(offsets + 4) as usize;
+    let pubkey_ix_index = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_size = read_u16(offsets + 10) as usize;
+    let message_ix_index = read_u16(offsets + 12);
+
+    // every piece has to live inside the sigverify instruction itself
+    require!(
+        signature_ix_index == u16::MAX && pubkey_ix_index == u16::MAX && message_ix_index == u16::MAX,
+        ErrorCode::InvalidAttestation
+    );
+
+    let signed_pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_SIZE)
+        .ok_or(ErrorCode::InvalidAttestation)?;
+    let signed_signature = data
+        .get(signature_offset..signature_offset + SIGNATURE_SIZE)
+        .ok_or(ErrorCode::InvalidAttestation)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidAttestation)?;
+
+    require!(signed_pubkey == attester.as_ref(), ErrorCode::InvalidAttestation);
+    require!(signed_signature == signature.as_slice(), ErrorCode::InvalidAttestation);
+    require!(signed_message == message, ErrorCode::InvalidAttestation);
+
+    Ok(())
+}
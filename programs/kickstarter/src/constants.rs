@@ -15,21 +15,62 @@ pub const SEED_QUOTE_VAULT: &str = "quote_vault";
 #[constant]
 pub const SEED_PRIVATE_STATE: &str = "private_state";
 
+#[constant]
+pub const SEED_NULLIFIER: &str = "nullifier";
+
+#[constant]
+pub const SEED_PRIVATE_DEPOSIT: &str = "private_deposit";
+
+#[constant]
+pub const SEED_PRIVATE_QUOTE_VAULT: &str = "private_quote_vault";
+
 #[constant]
 pub const BPS_DENOMINATOR: u64 = 10_000;
 
 #[constant]
-pub const LIQUIDITY_BPS: u64 = 2_000; // 20%
+pub const SEED_LIQUIDITY_POOL: &str = "liquidity_pool";
+
+#[constant]
+pub const SEED_POOL_BASE_VAULT: &str = "pool_base_vault";
 
-pub const MAX_PERFORMANCE_PACKAGES: usize = 5;
+#[constant]
+pub const SEED_POOL_QUOTE_VAULT: &str = "pool_quote_vault";
 
 #[constant]
-pub const BASE_TOKENS_FOR_INVESTORS_BPS: u64 = 10_000; // 100% - базовое значение для расчета
+pub const SEED_LP_MINT: &str = "lp_mint";
+
+#[constant]
+pub const SEED_LP_VAULT: &str = "lp_vault";
+
+#[constant]
+pub const POOL_FEE_BPS: u16 = 30; // 0.3% со свопа остается в пуле
+
+pub const MAX_PERFORMANCE_PACKAGES: usize = 20;
+
+#[constant]
+pub const SEED_PERFORMANCE_PACKAGE: &str = "performance_package";
+
+#[constant]
+pub const SEED_TREASURY_VAULT: &str = "treasury_vault";
+
+#[constant]
+pub const TEAM_BUDGET_PERIOD_SECONDS: i64 = 2_592_000; // 30 дней на один monthly_team_spending_usdc
 
 #[constant]
-pub const BASE_TOKENS_FOR_LIQUIDITY_BPS: u64 = 2_900; // 29% от базового supply для ликвидности
+pub const SEED_PRICE_OBSERVATIONS: &str = "price_observations";
+
+pub const MAX_PRICE_OBSERVATIONS: usize = 64;
+
+/// Observations a TWAP window must contain, half of what fits in it at min_interval.
+pub const MIN_TWAP_OBSERVATIONS: usize = MAX_PRICE_OBSERVATIONS / 4;
+
+#[constant]
+pub const BASE_TOKENS_FOR_INVESTORS_BPS: u64 = 10_000; // 100% - базовое значение для расчета
 
 #[constant]
 pub const BASE_TOKENS_FOR_PERFORMANCE_BPS: u64 = 10_000; // 100% от базового supply для performance pool (минимум)
 
-pub const DEFAULT_PACKAGE_UNLOCK_DELAY_SECONDS: i64 = 60;    
+pub const DEFAULT_PACKAGE_UNLOCK_DELAY_SECONDS: i64 = 60;
+
+#[constant]
+pub const COMPLETION_GRACE_PERIOD_SECONDS: i64 = 604_800; // 7 дней после дедлайна на complete, потом только refunds
@@ -62,4 +62,60 @@ pub enum ErrorCode {
     InvalidAttestedAmount,
     #[msg("Double spend detected")]
     DoubleSpend,
+    #[msg("Commitment tree is full")]
+    MerkleTreeFull,
+    #[msg("Private deposit balance is too low for this commitment")]
+    InsufficientPrivateDeposit,
+    #[msg("Private commitments are not settled into the quote vault")]
+    PrivateRoundNotSettled,
+    #[msg("Private round attestation is missing or not signed by the attester")]
+    InvalidAttestation,
+    #[msg("Raise reached the minimum and is still within the completion grace period")]
+    RaiseCanStillBeCompleted,
+    #[msg("Hard cap must cover the minimum raise and what is already committed")]
+    InvalidHardCap,
+    #[msg("Commitments can no longer be withdrawn this close to the deadline")]
+    WithdrawalWindowClosed,
+    #[msg("Withdrawal amount exceeds the committed amount")]
+    InsufficientCommitment,
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    #[msg("Nothing left to claim")]
+    NothingToClaim,
+    #[msg("Price feed account is not a verified price update for this raise")]
+    InvalidPriceFeed,
+    #[msg("Oracle price is too old")]
+    StalePrice,
+    #[msg("Oracle price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("TWAP window must be greater than zero")]
+    InvalidTwapWindow,
+    #[msg("Price was recorded too recently")]
+    PriceObservationTooSoon,
+    #[msg("Not enough price observations to cover the TWAP window")]
+    InsufficientPriceHistory,
+    #[msg("Performance package recipient is not set or does not own the destination account")]
+    InvalidPackageRecipient,
+    #[msg("Performance package has expired")]
+    PerformancePackageExpired,
+    #[msg("Performance package expiry has not been reached")]
+    PerformancePackageNotExpired,
+    #[msg("No performance pool tokens left to release")]
+    NothingToRelease,
+    #[msg("Pool has no liquidity for this trade")]
+    InsufficientLiquidity,
+    #[msg("Swap output is below the minimum amount out")]
+    SlippageExceeded,
+    #[msg("Liquidity is still locked")]
+    LiquidityStillLocked,
+    #[msg("Liquidity ratios must not exceed 100%")]
+    InvalidLiquidityConfig,
+    #[msg("Withdrawal exceeds the accrued team budget")]
+    TeamBudgetExceeded,
+    #[msg("Newest price observation is too old")]
+    StalePriceObservations,
+    #[msg("Liquidity pool has not been seeded yet")]
+    PoolNotSeeded,
+    #[msg("Private round state is delegated to the rollup")]
+    PrivateStateDelegated,
 }
@@ -10,6 +10,14 @@ pub struct FundEvent {
     pub total_committed: u64,
 }
 
+#[event]
+pub struct WithdrawEvent {
+    pub kickstarter: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub total_committed: u64,
+}
+
 #[event]
 pub struct RefundEvent {
     pub kickstarter: Pubkey,
@@ -35,12 +43,27 @@ pub struct CompleteEvent {
     pub treasury_amount: u64,
 }
 
+#[event]
+pub struct RefundsTriggeredEvent {
+    pub kickstarter: Pubkey,
+    /// public + private commitments when the raise moved to Refunding
+    pub total_committed: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MinimumRaiseUpdatedEvent {
     pub kickstarter: Pubkey,
     pub new_minimum: u64,
 }
 
+#[event]
+pub struct HardCapUpdatedEvent {
+    pub kickstarter: Pubkey,
+    pub new_hard_cap: u64,
+    pub close_on_hard_cap: bool,
+}
+
 #[event]
 pub struct StakeFromTreasuryEvent {
     pub kickstarter: Pubkey,
@@ -49,3 +72,53 @@ pub struct StakeFromTreasuryEvent {
     pub staking_account: Pubkey,
 }
 
+#[event]
+pub struct PerformanceTokensReleasedEvent {
+    pub kickstarter: Pubkey,
+    /// None for the unconfigured remainder of the pool
+    pub package_index: Option<u8>,
+    pub amount: u64,
+    pub burned: bool,
+}
+
+#[event]
+pub struct LiquidityPoolSeededEvent {
+    pub kickstarter: Pubkey,
+    pub pool: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub lp_amount: u64,
+}
+
+#[event]
+pub struct SwapEvent {
+    pub kickstarter: Pubkey,
+    pub user: Pubkey,
+    pub base_to_quote: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+#[event]
+pub struct LiquidityLockedEvent {
+    pub kickstarter: Pubkey,
+    pub lp_vault: Pubkey,
+    pub lp_amount: u64,
+    pub unlock_timestamp: i64,
+}
+
+#[event]
+pub struct LockedLiquidityWithdrawnEvent {
+    pub kickstarter: Pubkey,
+    pub lp_amount: u64,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+
+#[event]
+pub struct TeamBudgetWithdrawnEvent {
+    pub kickstarter: Pubkey,
+    pub amount: u64,
+    pub available_after: u64,
+    pub accrued_until: i64,
+}
@@ -39,13 +39,9 @@ pub fn handler(ctx: Context<Claim>) -> Result<()> {
     let funder_position = &mut ctx.accounts.funder_position;
 
     require!(kickstarter.state == KickstarterState::Complete, ErrorCode::InvalidKickstarterState);
+    require!(kickstarter.is_pool_seeded(), ErrorCode::PoolNotSeeded);
 
-    
-
-    // 1 - 1 ratio rn -> switch to "honest" distribution
-    //let claim_amount = funder_position.committed_amount.checked_sub(funder_position.already_claimed_base).unwrap();
-        
-    //switching к расчету доли of user которую он внес
+    // the funder's share of the investor tokens, pro rata to the completion snapshot
     let total_committed_snapshot = kickstarter
         .total_committed_at_completion
         .ok_or(ErrorCode::CommittedSnapshotMissing)?;
@@ -53,47 +49,57 @@ pub fn handler(ctx: Context<Claim>) -> Result<()> {
 
     let base_tokens_to_user_u128 = 
         (funder_position.committed_amount as u128)
-        .checked_mul(kickstarter.total_base_tokens_for_investors as u128).unwrap()
-        .checked_div(total_committed_snapshot as u128).unwrap();
+        .checked_mul(kickstarter.total_base_tokens_for_investors as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(total_committed_snapshot as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
     let base_tokens_to_user_u64 = u64::try_from(base_tokens_to_user_u128)
-        .unwrap(); //add error
+        .map_err(|_| ErrorCode::MathOverflow)?;
 
-    let tokens_to_claim = (base_tokens_to_user_u64)
-            .checked_sub(funder_position.already_claimed_base).unwrap(); //left to claim
+    // vesting runs from completion, unix_timestamp_closed is set there
+    let vesting_start = kickstarter
+        .unix_timestamp_closed
+        .ok_or(ErrorCode::InvalidKickstarterState)?;
+    let vested_tokens = kickstarter.vested_base_tokens(
+        base_tokens_to_user_u64,
+        vesting_start,
+        Clock::get()?.unix_timestamp,
+    )?;
 
-    if base_tokens_to_user_u64 > 0 {
+    let tokens_to_claim = vested_tokens.saturating_sub(funder_position.already_claimed_base); //left to claim
+    require!(tokens_to_claim > 0, ErrorCode::NothingToClaim);
 
-        let seeds = &[
-            b"kickstarter", 
-            kickstarter.kickstarter_authority.as_ref(),
-            kickstarter.base_mint.as_ref(), 
-            &[kickstarter.pda_bump]
-        ];
-        let signer = &[&seeds[..]];
+    let seeds = &[
+        b"kickstarter", 
+        kickstarter.kickstarter_authority.as_ref(),
+        kickstarter.base_mint.as_ref(), 
+        &[kickstarter.pda_bump]
+    ];
+    let signer = &[&seeds[..]];
 
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.base_vault.to_account_info(),
-            to: ctx.accounts.user_base_account.to_account_info(),
-            authority: kickstarter.to_account_info(),
-        };
-        let cpi_ctx: CpiContext<'_, '_, '_, '_, Transfer<'_>> = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(), 
-            cpi_accounts, 
-            signer
-        );
-        token::transfer(cpi_ctx, base_tokens_to_user_u64)?; //claim_amount
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.base_vault.to_account_info(),
+        to: ctx.accounts.user_base_account.to_account_info(),
+        authority: kickstarter.to_account_info(),
+    };
+    let cpi_ctx: CpiContext<'_, '_, '_, '_, Transfer<'_>> = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(), 
+        cpi_accounts, 
+        signer
+    );
+    token::transfer(cpi_ctx, tokens_to_claim)?;
 
-        //update сколько он забрал
-        funder_position.already_claimed_base = funder_position.already_claimed_base
-            .checked_add(tokens_to_claim).unwrap();
+    //update сколько он забрал
+    funder_position.already_claimed_base = funder_position.already_claimed_base
+        .checked_add(tokens_to_claim)
+        .ok_or(ErrorCode::MathOverflow)?;
 
-        emit!(ClaimEvent {
-            kickstarter: kickstarter.key(),
-            user: ctx.accounts.user.key(),
-            amount: tokens_to_claim,
-            total_claimed: funder_position.already_claimed_base,
-        });
-    }
+    emit!(ClaimEvent {
+        kickstarter: kickstarter.key(),
+        user: ctx.accounts.user.key(),
+        amount: tokens_to_claim,
+        total_claimed: funder_position.already_claimed_base,
+    });
 
     Ok(())
 }
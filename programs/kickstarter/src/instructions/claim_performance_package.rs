@@ -5,20 +5,25 @@ use anchor_spl::{
 };
 
 use crate::{
-    constants::{MAX_PERFORMANCE_PACKAGES, SEED_BASE_VAULT},
+    constants::{SEED_BASE_VAULT, SEED_PERFORMANCE_PACKAGE},
     error::ErrorCode,
-    state::{Kickstarter, KickstarterState},
+    state::{Kickstarter, KickstarterState, PerformancePackage},
 };
 
 #[derive(Accounts)]
+#[instruction(index: u8)]
 pub struct ClaimPerformancePackage<'info> {
+    /// the admin or the package recipient, tokens only ever go to the recipient
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
+    pub kickstarter: Account<'info, Kickstarter>,
     #[account(
         mut,
-        constraint = kickstarter.kickstarter_authority == admin.key() @ ErrorCode::InvalidAdmin,
+        seeds = [SEED_PERFORMANCE_PACKAGE.as_bytes(), kickstarter.key().as_ref(), &[index]],
+        bump = performance_package.bump,
+        has_one = kickstarter
     )]
-    pub kickstarter: Account<'info, Kickstarter>,
+    pub performance_package: Account<'info, PerformancePackage>,
     #[account(
         mut,
         address = kickstarter.base_vault,
@@ -31,33 +36,34 @@ pub struct ClaimPerformancePackage<'info> {
     pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(ctx: Context<ClaimPerformancePackage>, index: u8) -> Result<()> {
-    let index_usize = index as usize;
+pub fn handler(ctx: Context<ClaimPerformancePackage>, _index: u8) -> Result<()> {
+    let kickstarter = &ctx.accounts.kickstarter;
     require!(
-        index_usize < MAX_PERFORMANCE_PACKAGES,
-        ErrorCode::InvalidPerformancePackageIndex
+        kickstarter.state == KickstarterState::Complete,
+        ErrorCode::InvalidKickstarterState
     );
 
-    let kickstarter = &mut ctx.accounts.kickstarter;
+    let package = &ctx.accounts.performance_package;
+    let authority_key = ctx.accounts.authority.key();
     require!(
-        kickstarter.state == KickstarterState::Complete,
-        ErrorCode::InvalidKickstarterState
+        authority_key == kickstarter.kickstarter_authority || authority_key == package.recipient,
+        ErrorCode::Unauthorized
+    );
+    require!(
+        ctx.accounts.recipient_base_account.owner == package.recipient
+            && ctx.accounts.recipient_base_account.mint == kickstarter.base_mint,
+        ErrorCode::InvalidPackageRecipient
+    );
+    require!(package.is_unlocked, ErrorCode::PerformancePackageLocked);
+    require!(
+        package.claimed_amount < package.allocation,
+        ErrorCode::PerformancePackageAlreadyClaimed
     );
 
-    {
-        let package = &kickstarter.performance_packages[index_usize];
-        require!(
-            package.is_configured,
-            ErrorCode::PerformancePackageNotConfigured
-        );
-        require!(package.is_unlocked, ErrorCode::PerformancePackageLocked);
-        require!(
-            !package.is_claimed,
-            ErrorCode::PerformancePackageAlreadyClaimed
-        );
-    }
+    let vested = package.vested_amount(Clock::get()?.unix_timestamp)?;
+    let amount = vested.saturating_sub(package.claimed_amount);
+    require!(amount > 0, ErrorCode::NothingToClaim);
 
-    let amount = kickstarter.performance_packages[index_usize].allocation;
     let admin_key = kickstarter.kickstarter_authority;
     let base_mint_key = kickstarter.base_mint;
     let bump = kickstarter.pda_bump;
@@ -77,8 +83,11 @@ pub fn handler(ctx: Context<ClaimPerformancePackage>, index: u8) -> Result<()> {
     );
     token::transfer(cpi_ctx, amount)?;
 
-    let package = &mut kickstarter.performance_packages[index_usize];
-    package.is_claimed = true;
+    let package = &mut ctx.accounts.performance_package;
+    package.claimed_amount = package
+        .claimed_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
 
     Ok(())
 }
@@ -3,15 +3,15 @@ use anchor_spl::token::{self, Token, Mint, MintTo};
 use anchor_spl::token_interface::TokenAccount;
 
 use crate::{
+    oracle,
     events::CompleteEvent,
-    state::{Kickstarter, KickstarterState},
+    state::{Kickstarter, KickstarterState, PrivateFundState},
 };
 use crate::error::ErrorCode;
 use crate::constants::{
     BPS_DENOMINATOR, 
-    LIQUIDITY_BPS,
-    BASE_TOKENS_FOR_INVESTORS_BPS,
-    BASE_TOKENS_FOR_LIQUIDITY_BPS,
+    COMPLETION_GRACE_PERIOD_SECONDS,
+    SEED_PRIVATE_STATE,
 };
 
 #[derive(Accounts)]
@@ -25,60 +25,61 @@ pub struct CompleteKickstarter<'info> {
     pub kickstarter: Box<Account<'info, Kickstarter>>,
 
     #[account(
-        mut,
-        address = kickstarter.quote_vault
+        seeds = [SEED_PRIVATE_STATE.as_bytes(), kickstarter.key().as_ref()],
+        bump,
+        has_one = kickstarter
     )]
-    pub quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
-    
-    #[account(
-        mut,
-        constraint = treasury_token_account.owner == kickstarter.treasury @ ErrorCode::InvalidTreasuryAccountOwner,
-        constraint = treasury_token_account.mint == kickstarter.quote_mint @ ErrorCode::InvalidQuoteMint,
-    )]
-    pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub private_state: Box<Account<'info, PrivateFundState>>,
 
-    #[account(
-        mut,
-        constraint = liquidity_token_account.mint == kickstarter.quote_mint @ ErrorCode::InvalidQuoteMint,
-    )]
-    pub liquidity_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
-    
     #[account(
         mut,
         address = kickstarter.base_vault
     )]
     pub base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     
-    #[account(
-        mut,
-        constraint = liquidity_base_token_account.mint == kickstarter.base_mint @ ErrorCode::InvalidBaseMint,
-    )]
-    pub liquidity_base_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
-    
     #[account(
         mut,
         address = kickstarter.base_mint
     )]
     pub base_mint: Box<Account<'info, Mint>>,
-    
+
+    #[account(address = kickstarter.quote_mint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+
     pub token_program: Program<'info, Token>,
 }
 
 pub fn handler(ctx: Context<CompleteKickstarter>, final_raise_amount: u64) -> Result<()> {
     require!(ctx.accounts.kickstarter.state == KickstarterState::Live, ErrorCode::InvalidKickstarterState);
+    require!(
+        ctx.accounts.private_state.settled_amount == ctx.accounts.private_state.committed_amount,
+        ErrorCode::PrivateRoundNotSettled
+    );
 
     let current_time = Clock::get()?.unix_timestamp;
-        if let Some(closed_time) = ctx.accounts.kickstarter.unix_timestamp_closed {
-            if current_time < closed_time {
-            return err!(ErrorCode::TooEarlyToCompleteKickstarter);
-            }
-        }
+    let deadline = ctx.accounts.kickstarter
+        .unix_timestamp_closed
+        .ok_or(ErrorCode::InvalidKickstarterState)?;
+    if current_time < deadline {
+        return err!(ErrorCode::TooEarlyToCompleteKickstarter);
+    }
 
     let mut liquidity_amount: u64 = 0;
     let mut treasury_amount: u64 = 0;
     let mut final_raise_for_event: Option<u64> = None;
 
-    if ctx.accounts.kickstarter.total_committed_amount < ctx.accounts.kickstarter.minimum_raise_amount {
+    // public and private commitments are one raise: both count toward the minimum
+    // and share the same pro-rata snapshot
+    let total_committed = ctx.accounts.kickstarter.total_committed_amount
+        .checked_add(ctx.accounts.private_state.committed_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let grace_period_end = deadline
+        .checked_add(COMPLETION_GRACE_PERIOD_SECONDS)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let missed_grace_period = current_time >= grace_period_end;
+
+    if total_committed < ctx.accounts.kickstarter.minimum_raise_amount || missed_grace_period {
         ctx.accounts.kickstarter.state = KickstarterState::Refunding; //галя, у нас возврат
         ctx.accounts.kickstarter.final_raise_amount = None;
         ctx.accounts.kickstarter.total_committed_at_completion = None;
@@ -89,40 +90,31 @@ pub fn handler(ctx: Context<CompleteKickstarter>, final_raise_amount: u64) -> Re
             ErrorCode::InvalidFinalRaiseAmount
         );
         require!(
-            final_raise_amount <= ctx.accounts.kickstarter.total_committed_amount,
+            final_raise_amount <= total_committed,
             ErrorCode::FinalAmountExceedsTotalCommitted
         );
 
         let admin_key = ctx.accounts.kickstarter.kickstarter_authority;
         let base_mint_key = ctx.accounts.kickstarter.base_mint;
         let bump = ctx.accounts.kickstarter.pda_bump;
-        let total_committed = ctx.accounts.kickstarter.total_committed_amount;
         
         let base_tokens_for_investors_fixed = ctx.accounts.kickstarter.total_base_tokens_for_investors;
         let performance_pool_fixed = ctx.accounts.kickstarter.performance_pool_base_tokens;
         
-        let calculated_base_tokens_for_liquidity_u128 = (base_tokens_for_investors_fixed as u128)
-            .checked_mul(BASE_TOKENS_FOR_LIQUIDITY_BPS as u128)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(BASE_TOKENS_FOR_INVESTORS_BPS as u128)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let calculated_base_tokens_for_liquidity = u64::try_from(calculated_base_tokens_for_liquidity_u128)
-            .map_err(|_| ErrorCode::MathOverflow)?;
-        
         ctx.accounts.kickstarter.state = KickstarterState::Complete;
         ctx.accounts.kickstarter.final_raise_amount = Some(final_raise_amount);
         ctx.accounts.kickstarter.total_committed_at_completion = Some(total_committed);
         ctx.accounts.kickstarter.calculated_base_tokens_for_investors = Some(base_tokens_for_investors_fixed);
-        ctx.accounts.kickstarter.calculated_base_tokens_for_liquidity = Some(calculated_base_tokens_for_liquidity);
         ctx.accounts.kickstarter.calculated_performance_pool_tokens = Some(performance_pool_fixed);
         final_raise_for_event = Some(final_raise_amount);
         
-        let initial_price_u128 = (final_raise_amount as u128)
-            .checked_mul(1_000_000_000_000u128)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(base_tokens_for_investors_fixed as u128)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let initial_price = u64::try_from(initial_price_u128).map_err(|_| ErrorCode::MathOverflow)?;
+        let initial_price = oracle::token_price(
+            final_raise_amount,
+            base_tokens_for_investors_fixed,
+            ctx.accounts.base_mint.decimals,
+            ctx.accounts.quote_mint.decimals,
+            ctx.accounts.kickstarter.token_price_decimals,
+        )?;
         ctx.accounts.kickstarter.initial_token_price = Some(initial_price);
         
         let seeds = &[
@@ -134,11 +126,16 @@ pub fn handler(ctx: Context<CompleteKickstarter>, final_raise_amount: u64) -> Re
         let signer = &[&seeds[..]];
 
         let liquidity_amount_u128 = (final_raise_amount as u128)
-            .checked_mul(LIQUIDITY_BPS as u128)
+            .checked_mul(ctx.accounts.kickstarter.liquidity_bps as u128)
             .ok_or(ErrorCode::MathOverflow)?
             .checked_div(BPS_DENOMINATOR as u128)
             .ok_or(ErrorCode::MathOverflow)?;
         liquidity_amount = u64::try_from(liquidity_amount_u128).map_err(|_| ErrorCode::MathOverflow)?;
+        let calculated_base_tokens_for_liquidity = ctx
+            .accounts
+            .kickstarter
+            .base_tokens_for_liquidity(liquidity_amount, final_raise_amount)?;
+        ctx.accounts.kickstarter.calculated_base_tokens_for_liquidity = Some(calculated_base_tokens_for_liquidity);
         
         let monthly_spending = ctx.accounts.kickstarter.monthly_team_spending_usdc;
         
@@ -151,22 +148,11 @@ pub fn handler(ctx: Context<CompleteKickstarter>, final_raise_amount: u64) -> Re
             ErrorCode::MathOverflow
         );
         
-        treasury_amount = remaining_after_liquidity
-            .checked_sub(monthly_spending)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        if liquidity_amount > 0 {
-            let cpi_ctx_liquidity = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
-                    from: ctx.accounts.quote_vault.to_account_info(),
-                    to: ctx.accounts.liquidity_token_account.to_account_info(),
-                    authority: ctx.accounts.kickstarter.to_account_info(),
-                },
-                signer,
-            );
-            token::transfer(cpi_ctx_liquidity, liquidity_amount)?;
-        }
+        // seed_pool moves the rest into the treasury vault, the budget accrues from
+        // here one month at a time
+        treasury_amount = remaining_after_liquidity;
+        ctx.accounts.kickstarter.team_budget_accrued_until = Some(current_time);
+        ctx.accounts.kickstarter.team_budget_available = 0;
 
         let total_base_to_mint = base_tokens_for_investors_fixed
             .checked_add(calculated_base_tokens_for_liquidity)
@@ -187,46 +173,6 @@ pub fn handler(ctx: Context<CompleteKickstarter>, final_raise_amount: u64) -> Re
             token::mint_to(cpi_ctx_mint_to_vault, total_base_to_mint)?;
         }
 
-        if calculated_base_tokens_for_liquidity > 0 {
-            let cpi_ctx_base_liquidity = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
-                    from: ctx.accounts.base_vault.to_account_info(),
-                    to: ctx.accounts.liquidity_base_token_account.to_account_info(),
-                    authority: ctx.accounts.kickstarter.to_account_info(),
-                },
-                signer,
-            );
-            token::transfer(cpi_ctx_base_liquidity, calculated_base_tokens_for_liquidity)?;
-        }
-
-        if monthly_spending > 0 {
-            let cpi_ctx_monthly_spending = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
-                    from: ctx.accounts.quote_vault.to_account_info(),
-                    to: ctx.accounts.treasury_token_account.to_account_info(),
-                    authority: ctx.accounts.kickstarter.to_account_info(),
-                },
-                signer,
-            );
-            token::transfer(cpi_ctx_monthly_spending, monthly_spending)?;
-        }
-
-        if treasury_amount > 0 {
-            let cpi_ctx_treasury = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(), 
-                token::Transfer {
-                    from: ctx.accounts.quote_vault.to_account_info(),
-                    to: ctx.accounts.treasury_token_account.to_account_info(), 
-                    authority: ctx.accounts.kickstarter.to_account_info(),
-                },
-                signer
-            );
-            
-            token::transfer(cpi_ctx_treasury, treasury_amount)?;
-        }
-        
         ctx.accounts.kickstarter.calculated_liquidity_amount = Some(liquidity_amount);
     }
 
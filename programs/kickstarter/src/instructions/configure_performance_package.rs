@@ -1,12 +1,13 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    constants::MAX_PERFORMANCE_PACKAGES,
+    constants::{MAX_PERFORMANCE_PACKAGES, SEED_PERFORMANCE_PACKAGE},
     error::ErrorCode,
-    state::{Kickstarter, KickstarterState},
+    state::{Kickstarter, KickstarterState, PerformancePackage},
 };
 
 #[derive(Accounts)]
+#[instruction(index: u8)]
 pub struct ConfigurePerformancePackage<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -16,24 +17,40 @@ pub struct ConfigurePerformancePackage<'info> {
         constraint = kickstarter.state == KickstarterState::Initialized @ ErrorCode::InvalidKickstarterState,
     )]
     pub kickstarter: Account<'info, Kickstarter>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PerformancePackage::INIT_SPACE,
+        seeds = [SEED_PERFORMANCE_PACKAGE.as_bytes(), kickstarter.key().as_ref(), &[index]],
+        bump
+    )]
+    pub performance_package: Account<'info, PerformancePackage>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(
     ctx: Context<ConfigurePerformancePackage>,
     index: u8,
-    multiplier: u8,
+    multiplier_bps: u32,
     allocation: u64,
+    recipient: Pubkey,
+    vesting_duration_seconds: u32,
+    depends_on_previous: bool,
 ) -> Result<()> {
     require!(allocation > 0, ErrorCode::InvalidPerformancePackageAllocation);
-    require!(multiplier > 0, ErrorCode::InvalidPerformancePackageAllocation);
+    require!(multiplier_bps > 0, ErrorCode::InvalidPerformancePackageAllocation);
+    require!(recipient != Pubkey::default(), ErrorCode::InvalidPackageRecipient);
 
+    let kickstarter = &mut ctx.accounts.kickstarter;
+    // packages are added one after another so that index - 1 always exists
     let index_usize = index as usize;
     require!(
-        index_usize < MAX_PERFORMANCE_PACKAGES,
+        index_usize < MAX_PERFORMANCE_PACKAGES
+            && index == kickstarter.performance_package_count
+            && (index > 0 || !depends_on_previous),
         ErrorCode::InvalidPerformancePackageIndex
     );
 
-    let kickstarter = &mut ctx.accounts.kickstarter;
     let new_total = kickstarter
         .configured_performance_tokens
         .checked_add(allocation)
@@ -43,20 +60,22 @@ pub fn handler(
         ErrorCode::PerformancePoolExceeded
     );
 
-    let package = &mut kickstarter.performance_packages[index_usize];
-    require!(
-        !package.is_configured,
-        ErrorCode::PerformancePackageAlreadyConfigured
-    );
-
-    package.multiplier = multiplier;
+    let package = &mut ctx.accounts.performance_package;
+    package.kickstarter = kickstarter.key();
+    package.index = index;
+    package.multiplier_bps = multiplier_bps;
     package.allocation = allocation;
-    package.is_configured = true;
+    package.depends_on_previous = depends_on_previous;
     package.is_unlocked = false;
-    package.is_claimed = false;
+    package.unlocked_at = None;
+    package.recipient = recipient;
+    package.vesting_duration_seconds = vesting_duration_seconds;
+    package.claimed_amount = 0;
+    package.is_expired = false;
+    package.bump = ctx.bumps.performance_package;
 
     kickstarter.configured_performance_tokens = new_total;
+    kickstarter.performance_package_count = index + 1;
 
     Ok(())
 }
-
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{self, Token, Transfer},
+    token_interface::TokenAccount as SplTokenAccount,
+};
+
+use crate::state::{Kickstarter, KickstarterState, PrivateDeposit};
+use crate::error::ErrorCode;
+use crate::constants::{SEED_PRIVATE_DEPOSIT, SEED_PRIVATE_QUOTE_VAULT};
+
+#[derive(Accounts)]
+pub struct DepositPrivate<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub kickstarter: Account<'info, Kickstarter>,
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + PrivateDeposit::INIT_SPACE,
+        seeds = [SEED_PRIVATE_DEPOSIT.as_bytes(), kickstarter.key().as_ref(), funder.key().as_ref()],
+        bump
+    )]
+    pub private_deposit: Account<'info, PrivateDeposit>,
+    #[account(mut)]
+    pub funder_quote_account: InterfaceAccount<'info, SplTokenAccount>,
+    #[account(
+        mut,
+        address = kickstarter.private_quote_vault,
+        seeds = [SEED_PRIVATE_QUOTE_VAULT.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub private_quote_vault: InterfaceAccount<'info, SplTokenAccount>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DepositPrivate>, amount: u64) -> Result<()> {
+    let kickstarter = &ctx.accounts.kickstarter;
+    let private_deposit = &mut ctx.accounts.private_deposit;
+
+    require!(kickstarter.state == KickstarterState::Live, ErrorCode::InvalidKickstarterState);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.funder_quote_account.to_account_info(),
+        to: ctx.accounts.private_quote_vault.to_account_info(),
+        authority: ctx.accounts.funder.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)?;
+
+    if private_deposit.kickstarter == Pubkey::default() {
+        private_deposit.kickstarter = kickstarter.key();
+        private_deposit.owner = ctx.accounts.funder.key();
+        private_deposit.bump = ctx.bumps.private_deposit;
+    }
+
+    private_deposit.balance = private_deposit
+        .balance
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    msg!("Private deposit: balance {}", private_deposit.balance);
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+use anchor_spl::token_interface::TokenAccount;
+
+use super::release_unconfigured_performance_tokens::release_base_tokens;
+use crate::{
+    constants::SEED_PERFORMANCE_PACKAGE,
+    events::PerformanceTokensReleasedEvent,
+    error::ErrorCode,
+    state::{Kickstarter, KickstarterState, PerformancePackage, PerformanceTokenRelease},
+};
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct ExpirePerformancePackage<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        constraint = kickstarter.kickstarter_authority == admin.key() @ ErrorCode::InvalidAdmin
+    )]
+    pub kickstarter: Box<Account<'info, Kickstarter>>,
+    #[account(
+        mut,
+        seeds = [SEED_PERFORMANCE_PACKAGE.as_bytes(), kickstarter.key().as_ref(), &[index]],
+        bump = performance_package.bump,
+        has_one = kickstarter
+    )]
+    pub performance_package: Account<'info, PerformancePackage>,
+    #[account(mut, address = kickstarter.base_vault)]
+    pub base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = kickstarter.base_mint)]
+    pub base_mint: Box<Account<'info, Mint>>,
+    /// receives the expired package's allocation with `PerformanceTokenRelease::Treasury`,
+    /// can be left out when burning it
+    #[account(
+        mut,
+        constraint = treasury_base_account.owner == kickstarter.treasury @ ErrorCode::InvalidTreasuryAccountOwner,
+        constraint = treasury_base_account.mint == kickstarter.base_mint @ ErrorCode::InvalidBaseMint,
+    )]
+    pub treasury_base_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(
+    ctx: Context<ExpirePerformancePackage>,
+    index: u8,
+    release: PerformanceTokenRelease,
+) -> Result<()> {
+    let kickstarter = &ctx.accounts.kickstarter;
+    require!(
+        kickstarter.state == KickstarterState::Complete,
+        ErrorCode::InvalidKickstarterState
+    );
+
+    let package = &ctx.accounts.performance_package;
+    require!(!package.is_expired, ErrorCode::PerformancePackageExpired);
+    require!(
+        !package.is_unlocked,
+        ErrorCode::PerformancePackageAlreadyUnlocked
+    );
+    require!(
+        kickstarter.performance_packages_expired(Clock::get()?.unix_timestamp)?,
+        ErrorCode::PerformancePackageNotExpired
+    );
+
+    let amount = package.allocation;
+    release_base_tokens(
+        kickstarter,
+        release,
+        amount,
+        ctx.accounts.base_vault.to_account_info(),
+        ctx.accounts.base_mint.to_account_info(),
+        ctx.accounts.treasury_base_account.as_ref().map(|account| account.to_account_info()),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    ctx.accounts.performance_package.is_expired = true;
+
+    emit!(PerformanceTokensReleasedEvent {
+        kickstarter: kickstarter.key(),
+        package_index: Some(index),
+        amount,
+        burned: release == PerformanceTokenRelease::Burn,
+    });
+
+    Ok(())
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{self, Token, Transfer},
+    token_interface::TokenAccount as SplTokenAccount,
+};
 
+use crate::attestation::{attestation_message, verify_ed25519_attestation, INSTRUCTIONS_SYSVAR_ID};
 use crate::state::{Kickstarter, KickstarterState, PrivateFundState};
 use crate::error::ErrorCode;
-use crate::constants::SEED_PRIVATE_STATE;
+use crate::constants::{SEED_PRIVATE_QUOTE_VAULT, SEED_PRIVATE_STATE, SEED_QUOTE_VAULT};
 
 #[derive(Accounts)]
 pub struct FinalizePrivateRound<'info> {
@@ -20,16 +25,34 @@ pub struct FinalizePrivateRound<'info> {
         has_one = kickstarter
     )]
     pub private_state: Account<'info, PrivateFundState>,
+    #[account(
+        mut,
+        address = kickstarter.private_quote_vault,
+        seeds = [SEED_PRIVATE_QUOTE_VAULT.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub private_quote_vault: InterfaceAccount<'info, SplTokenAccount>,
+    #[account(
+        mut,
+        address = kickstarter.quote_vault,
+        seeds = [SEED_QUOTE_VAULT.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: InterfaceAccount<'info, SplTokenAccount>,
+    pub token_program: Program<'info, Token>,
+    /// CHECK: address is checked, holds the Ed25519 sigverify of the attestation
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
 }
 
 pub fn handler(
     ctx: Context<FinalizePrivateRound>,
     final_commitments_root: [u8; 32],
     attested_total_amount: u64,
-    attestation_signature: [u8; 64], // Simplified - in production this should be verified
+    attestation_signature: [u8; 64],
 ) -> Result<()> {
     let kickstarter = &mut ctx.accounts.kickstarter;
-    let private_state = &ctx.accounts.private_state;
+    let private_state = &mut ctx.accounts.private_state;
 
     require!(kickstarter.state == KickstarterState::Live, ErrorCode::InvalidKickstarterState);
 
@@ -43,8 +66,44 @@ pub fn handler(
         ErrorCode::InvalidAttestedAmount
     );
 
+    let message = attestation_message(&kickstarter.key(), &final_commitments_root, attested_total_amount);
+    verify_ed25519_attestation(
+        &ctx.accounts.instructions_sysvar.to_account_info(),
+        &kickstarter.private_round_attester,
+        &message,
+        &attestation_signature,
+    )?;
+
     kickstarter.is_private_round_active = false;
 
+    // committed funds leave the private escrow and back the raise like public ones
+    let unsettled_amount = private_state
+        .committed_amount
+        .checked_sub(private_state.settled_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if unsettled_amount > 0 {
+        let seeds = &[
+            b"kickstarter",
+            kickstarter.kickstarter_authority.as_ref(),
+            kickstarter.base_mint.as_ref(),
+            &[kickstarter.pda_bump]
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.private_quote_vault.to_account_info(),
+            to: ctx.accounts.quote_vault.to_account_info(),
+            authority: kickstarter.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer
+        );
+        token::transfer(cpi_ctx, unsettled_amount)?;
+    }
+    private_state.settled_amount = private_state.committed_amount;
+
     msg!(
         "Private round finalized: root={:?}, total_amount={}, investors={}",
         final_commitments_root,
@@ -5,10 +5,10 @@ use anchor_spl::{
 };
 
 use crate::events::FundEvent;
-use crate::state::{FunderPosition, Kickstarter, KickstarterState};
+use crate::state::{FunderPosition, Kickstarter, KickstarterState, PrivateFundState};
 use crate::error::ErrorCode;
 
-use crate::constants::SEED_QUOTE_VAULT;
+use crate::constants::{SEED_PRIVATE_STATE, SEED_QUOTE_VAULT};
 
 #[derive(Accounts)]
 pub struct Fund<'info> {
@@ -24,6 +24,13 @@ pub struct Fund<'info> {
         bump
     )]
     pub funder_position: Account<'info, FunderPosition>,
+    /// CHECK: its committed amount shares the hard cap with public funding, which
+    /// is refused while the account is delegated to the rollup
+    #[account(
+        seeds = [SEED_PRIVATE_STATE.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub private_state: UncheckedAccount<'info>,
     #[account(mut)]
     pub funder_quote_account: InterfaceAccount<'info, SplTokenAccount>,
     #[account(
@@ -43,16 +50,20 @@ pub fn handler(ctx: Context<Fund>, amount: u64) -> Result<()> {
     
     require!(kickstarter.state == KickstarterState::Live, ErrorCode::InvalidKickstarterState);
 
-    if let Some(closed_time) = kickstarter.unix_timestamp_closed {
-        if Clock::get()?.unix_timestamp < closed_time {
-            return err!(ErrorCode::TooEarlyToCompleteKickstarter);
+    if let Some(deadline) = kickstarter.unix_timestamp_closed {
+        if Clock::get()?.unix_timestamp >= deadline {
+            return err!(ErrorCode::FundingTimeIsOut);
         }
     }
-    if kickstarter.total_committed_amount.checked_add(amount) > Some(kickstarter.hard_cap) {
-        return err!(ErrorCode::OverHardcapLimit);
-    }
-    //TODO make partial refund, it just resigns the current depo for now
-
+    // the deposit that crosses the cap is only taken up to the cap, the rest
+    // never leaves the funder's account
+    let private_committed_amount =
+        PrivateFundState::base_layer_committed_amount(&ctx.accounts.private_state)?;
+    let remaining_capacity = kickstarter
+        .hard_cap
+        .saturating_sub(kickstarter.combined_committed_amount(private_committed_amount)?);
+    require!(remaining_capacity > 0, ErrorCode::OverHardcapLimit);
+    let amount = amount.min(remaining_capacity);
 
     let cpi_accounts = Transfer {
         from: ctx.accounts.funder_quote_account.to_account_info(),
@@ -62,7 +73,9 @@ pub fn handler(ctx: Context<Fund>, amount: u64) -> Result<()> {
     let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
     token::transfer(cpi_ctx, amount)?;
     
-    kickstarter.total_committed_amount = kickstarter.total_committed_amount.checked_add(amount).unwrap();
+    kickstarter.total_committed_amount = kickstarter.total_committed_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
     
     if funder_position.kickstarter == Pubkey::default() {
         funder_position.kickstarter = kickstarter.key();
@@ -70,7 +83,16 @@ pub fn handler(ctx: Context<Fund>, amount: u64) -> Result<()> {
         funder_position.bump = ctx.bumps.funder_position;
     }
 
-    funder_position.committed_amount = funder_position.committed_amount.checked_add(amount).unwrap();
+    funder_position.committed_amount = funder_position.committed_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    if kickstarter.close_on_hard_cap
+        && kickstarter.combined_committed_amount(private_committed_amount)? == kickstarter.hard_cap
+    {
+        kickstarter.unix_timestamp_closed = Some(Clock::get()?.unix_timestamp);
+        msg!("Hard cap reached, funding closed");
+    }
 
     emit!(FundEvent {
         kickstarter: kickstarter.key(),
@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
-use sha2::{Digest, Sha256};
 use ephemeral_rollups_sdk::anchor::commit;
 use ephemeral_rollups_sdk::ephem::commit_and_undelegate_accounts;
 
-use crate::state::{Kickstarter, KickstarterState, PrivateFundState};
+use crate::state::{Kickstarter, KickstarterState, PrivateDeposit, PrivateFundState};
 use crate::error::ErrorCode;
-use crate::constants::SEED_PRIVATE_STATE;
+use crate::constants::{SEED_PRIVATE_DEPOSIT, SEED_PRIVATE_STATE};
+use crate::merkle_tree::{append_leaf, commitment_hash};
 
 #[commit]
 #[derive(Accounts)]
@@ -20,51 +20,65 @@ pub struct FundPrivate<'info> {
         has_one = kickstarter
     )]
     pub private_state: Account<'info, PrivateFundState>,
+    #[account(
+        mut,
+        seeds = [SEED_PRIVATE_DEPOSIT.as_bytes(), kickstarter.key().as_ref(), funder.key().as_ref()],
+        bump = private_deposit.bump,
+        has_one = kickstarter
+    )]
+    pub private_deposit: Account<'info, PrivateDeposit>,
 }
 
 pub fn handler(ctx: Context<FundPrivate>, amount: u64, salt: [u8; 32]) -> Result<()> {
     let kickstarter = &ctx.accounts.kickstarter;
     let private_state = &mut ctx.accounts.private_state;
-
+    let private_deposit = &mut ctx.accounts.private_deposit;
 
     require!(kickstarter.state == KickstarterState::Live, ErrorCode::InvalidKickstarterState);
     require!(kickstarter.is_private_round_active, ErrorCode::InvalidKickstarterState);
 
-    if let Some(closed_time) = kickstarter.unix_timestamp_closed {
-        if Clock::get()?.unix_timestamp < closed_time {
-            return err!(ErrorCode::TooEarlyToCompleteKickstarter);
+    if let Some(deadline) = kickstarter.unix_timestamp_closed {
+        if Clock::get()?.unix_timestamp >= deadline {
+            return err!(ErrorCode::FundingTimeIsOut);
         }
     }
 
-    if private_state.committed_amount.checked_add(amount) > Some(kickstarter.hard_cap) {
+    let committed_amount = private_state
+        .committed_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if kickstarter.combined_committed_amount(committed_amount)? > kickstarter.hard_cap {
         return err!(ErrorCode::OverHardcapLimit);
     }
-    private_state.committed_amount = private_state.committed_amount.checked_add(amount).unwrap();
-
-    let mut hasher = Sha256::new();
-    hasher.update(ctx.accounts.funder.key().as_ref());
-    hasher.update(&amount.to_le_bytes());
-    hasher.update(&salt);
-    let commitment_hash: [u8; 32] = hasher.finalize().into();
+    require!(private_deposit.balance >= amount, ErrorCode::InsufficientPrivateDeposit);
+    private_deposit.balance -= amount;
+    private_state.committed_amount = committed_amount;
 
-    let mut new_root = [0u8; 32];
-    if private_state.investor_count == 0 {
-        new_root = commitment_hash;
-    } else {
-        let mut hasher = Sha256::new();
-        hasher.update(private_state.commitments_root);
-        hasher.update(commitment_hash);
-        new_root = hasher.finalize().into();
-    }
-    private_state.commitments_root = new_root;
+    let leaf_index = private_state.investor_count;
+    let commitment = commitment_hash(&ctx.accounts.funder.key(), amount, &salt);
+    private_state.commitments_root = append_leaf(
+        &mut private_state.filled_subtrees,
+        leaf_index,
+        commitment,
+    )?;
 
-    private_state.investor_count = private_state.investor_count.checked_add(1).unwrap();
+    private_state.investor_count = private_state
+        .investor_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
 
-    msg!("Private funding: commitment added, total committed: {}", private_state.committed_amount);
+    msg!(
+        "Private funding: commitment added at leaf {}, total committed: {}",
+        leaf_index,
+        private_state.committed_amount
+    );
 
     commit_and_undelegate_accounts(
         &ctx.accounts.funder,
-        vec![&ctx.accounts.private_state.to_account_info()],
+        vec![
+            &ctx.accounts.private_state.to_account_info(),
+            &ctx.accounts.private_deposit.to_account_info(),
+        ],
         &ctx.accounts.magic_context,
         &ctx.accounts.magic_program,
     )?;
@@ -10,8 +10,14 @@ use mpl_token_metadata::{
     types::DataV2,
 };  
 
-use crate::state::{Kickstarter, KickstarterState, PerformancePackage, PrivateFundState};
-use crate::constants::{SEED_BASE_VAULT, SEED_QUOTE_VAULT, SEED_PRIVATE_STATE, MAX_PERFORMANCE_PACKAGES};
+use crate::state::{Kickstarter, KickstarterState, PrivateFundState};
+use crate::error::ErrorCode;
+use crate::constants::{
+    SEED_BASE_VAULT, SEED_QUOTE_VAULT, SEED_PRIVATE_STATE, SEED_PRIVATE_QUOTE_VAULT,
+    BPS_DENOMINATOR,
+};
+use crate::merkle_tree::{empty_root, MERKLE_TREE_DEPTH};
+use crate::oracle::TOKEN_PRICE_DECIMALS;
 
 #[derive(Accounts)]
 pub struct InitializeKickstarter<'info> {
@@ -45,6 +51,15 @@ pub struct InitializeKickstarter<'info> {
         bump
     )]
     pub quote_vault: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = admin,
+        token::mint = quote_mint,
+        token::authority = kickstarter,
+        seeds = [SEED_PRIVATE_QUOTE_VAULT.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub private_quote_vault: Box<Account<'info, TokenAccount>>,
     #[account(
         init,
         payer = admin,
@@ -68,6 +83,7 @@ pub struct InitializeKickstarter<'info> {
     pub token_metadata_program: UncheckedAccount<'info>, //unchecked- можно не грузить бинарник в тестах
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<InitializeKickstarter>,
     minimum_raise_amount: u64,
@@ -80,7 +96,34 @@ pub fn handler(
     token_symbol: String,
     _token_description: String, 
     token_image_url: String,
+    private_round_attester: Pubkey,
+    hard_cap: u64,
+    close_on_hard_cap: bool,
+    withdrawal_cutoff_seconds: u32,
+    vesting_tge_bps: u16,
+    vesting_cliff_seconds: u32,
+    vesting_duration_seconds: u32,
+    price_feed: Pubkey,
+    price_feed_id: [u8; 32],
+    max_price_age_seconds: u32,
+    max_price_confidence_bps: u16,
+    twap_window_seconds: u32,
+    performance_package_expiry_seconds: u32,
+    liquidity_lock_seconds: u32,
+    liquidity_bps: u16,
+    base_tokens_for_liquidity_bps: u16,
 ) -> Result<()> {
+    require!(hard_cap >= minimum_raise_amount, ErrorCode::InvalidHardCap);
+    require!(
+        vesting_tge_bps as u64 <= BPS_DENOMINATOR && vesting_cliff_seconds <= vesting_duration_seconds,
+        ErrorCode::InvalidVestingSchedule
+    );
+    require!(twap_window_seconds > 0, ErrorCode::InvalidTwapWindow);
+    require!(
+        liquidity_bps as u64 <= BPS_DENOMINATOR && base_tokens_for_liquidity_bps as u64 <= BPS_DENOMINATOR,
+        ErrorCode::InvalidLiquidityConfig
+    );
+
     let kickstarter = &mut ctx.accounts.kickstarter;
     
     kickstarter.kickstarter_authority = ctx.accounts.admin.key();
@@ -89,13 +132,14 @@ pub fn handler(
     kickstarter.quote_mint = ctx.accounts.quote_mint.key();
     kickstarter.base_vault = ctx.accounts.base_vault.key();
     kickstarter.quote_vault = ctx.accounts.quote_vault.key();
+    kickstarter.private_quote_vault = ctx.accounts.private_quote_vault.key();
     kickstarter.treasury = ctx.accounts.treasury.key();
     kickstarter.minimum_raise_amount = minimum_raise_amount;
-    kickstarter.hard_cap = u64::MAX;
+    kickstarter.hard_cap = hard_cap;
     kickstarter.total_base_tokens_for_investors = total_base_tokens_for_investors;
     kickstarter.performance_pool_base_tokens = performance_pool_base_tokens;
     kickstarter.configured_performance_tokens = 0;
-    kickstarter.performance_packages = [PerformancePackage::default(); MAX_PERFORMANCE_PACKAGES];
+    kickstarter.performance_package_count = 0;
     kickstarter.seconds_for_launch = seconds_for_launch;
     kickstarter.total_committed_amount = 0;
     kickstarter.pda_bump = ctx.bumps.kickstarter;
@@ -113,12 +157,35 @@ pub fn handler(
     kickstarter.private_commitments_root = [0u8; 32];
     kickstarter.private_investor_count = 0;
     kickstarter.is_private_round_active = false;
+    kickstarter.private_round_attester = private_round_attester;
+    kickstarter.close_on_hard_cap = close_on_hard_cap;
+    kickstarter.withdrawal_cutoff_seconds = withdrawal_cutoff_seconds;
+    kickstarter.vesting_tge_bps = vesting_tge_bps;
+    kickstarter.vesting_cliff_seconds = vesting_cliff_seconds;
+    kickstarter.vesting_duration_seconds = vesting_duration_seconds;
+    kickstarter.price_feed = price_feed;
+    kickstarter.price_feed_id = price_feed_id;
+    kickstarter.max_price_age_seconds = max_price_age_seconds;
+    kickstarter.max_price_confidence_bps = max_price_confidence_bps;
+    kickstarter.twap_window_seconds = twap_window_seconds;
+    kickstarter.performance_package_expiry_seconds = performance_package_expiry_seconds;
+    kickstarter.unconfigured_performance_released = false;
+    kickstarter.liquidity_lock_seconds = liquidity_lock_seconds;
+    kickstarter.liquidity_unlock_timestamp = None;
+    kickstarter.liquidity_bps = liquidity_bps;
+    kickstarter.base_tokens_for_liquidity_bps = base_tokens_for_liquidity_bps;
+    kickstarter.token_price_decimals = TOKEN_PRICE_DECIMALS;
+    kickstarter.treasury_vault = Pubkey::default();
+    kickstarter.team_budget_accrued_until = None;
+    kickstarter.team_budget_available = 0;
 
     let private_state = &mut ctx.accounts.private_state;
     private_state.kickstarter = kickstarter.key();
-    private_state.commitments_root = [0u8; 32];
+    private_state.commitments_root = empty_root();
+    private_state.filled_subtrees = [[0u8; 32]; MERKLE_TREE_DEPTH];
     private_state.investor_count = 0;
     private_state.committed_amount = 0;
+    private_state.settled_amount = 0;
     private_state.bump = ctx.bumps.private_state;
 
     let admin_key = ctx.accounts.admin.key();
@@ -1,7 +1,10 @@
 pub mod initialize_kickstarter;
 pub mod start_kickstarter;
 pub mod fund;
+pub mod withdraw_commitment;
 pub mod fund_private;
+pub mod deposit_private;
+pub mod withdraw_private_deposit;
 pub mod finalize_private_round;
 pub mod private_claim;
 pub mod private_claim_compressed;
@@ -11,13 +14,22 @@ pub mod start_private_round;
 pub mod end_private_round;
 pub mod close_kickstarter;
 pub mod complete_kickstarter;
+pub mod trigger_refunds;
 pub mod claim;
 pub mod refund;
 pub mod configure_performance_package;
 pub mod unlock_performance_package;
+pub mod record_price;
 pub mod claim_performance_package;
+pub mod release_unconfigured_performance_tokens;
+pub mod expire_performance_package;
 pub mod set_minimum_raise;
+pub mod set_hard_cap;
 pub mod stake_from_treasury;
+pub mod seed_pool;
+pub mod swap;
+pub mod withdraw_locked_liquidity;
+pub mod withdraw_team_budget;
 pub mod permissions;
 
 pub use claim::*;
@@ -41,3 +53,54 @@ pub use unlock_performance_package::*;
 pub use set_minimum_raise::*;
 pub use stake_from_treasury::*;
 pub use permissions::*;
+
+// re-exported by name so `handler` isn't glob re-exported once more; #[program]
+// also looks up the generated client modules at the crate root
+pub use trigger_refunds::TriggerRefunds;
+pub(crate) use trigger_refunds::__client_accounts_trigger_refunds;
+#[cfg(feature = "cpi")]
+pub(crate) use trigger_refunds::__cpi_client_accounts_trigger_refunds;
+pub use release_unconfigured_performance_tokens::ReleaseUnconfiguredPerformanceTokens;
+pub(crate) use release_unconfigured_performance_tokens::__client_accounts_release_unconfigured_performance_tokens;
+#[cfg(feature = "cpi")]
+pub(crate) use release_unconfigured_performance_tokens::__cpi_client_accounts_release_unconfigured_performance_tokens;
+pub use expire_performance_package::ExpirePerformancePackage;
+pub(crate) use expire_performance_package::__client_accounts_expire_performance_package;
+#[cfg(feature = "cpi")]
+pub(crate) use expire_performance_package::__cpi_client_accounts_expire_performance_package;
+pub use withdraw_commitment::WithdrawCommitment;
+pub(crate) use withdraw_commitment::__client_accounts_withdraw_commitment;
+#[cfg(feature = "cpi")]
+pub(crate) use withdraw_commitment::__cpi_client_accounts_withdraw_commitment;
+pub use deposit_private::DepositPrivate;
+pub(crate) use deposit_private::__client_accounts_deposit_private;
+#[cfg(feature = "cpi")]
+pub(crate) use deposit_private::__cpi_client_accounts_deposit_private;
+pub use withdraw_private_deposit::WithdrawPrivateDeposit;
+pub(crate) use withdraw_private_deposit::__client_accounts_withdraw_private_deposit;
+#[cfg(feature = "cpi")]
+pub(crate) use withdraw_private_deposit::__cpi_client_accounts_withdraw_private_deposit;
+pub use record_price::RecordPrice;
+pub(crate) use record_price::__client_accounts_record_price;
+#[cfg(feature = "cpi")]
+pub(crate) use record_price::__cpi_client_accounts_record_price;
+pub use set_hard_cap::SetHardCap;
+pub(crate) use set_hard_cap::__client_accounts_set_hard_cap;
+#[cfg(feature = "cpi")]
+pub(crate) use set_hard_cap::__cpi_client_accounts_set_hard_cap;
+pub use seed_pool::SeedPool;
+pub(crate) use seed_pool::__client_accounts_seed_pool;
+#[cfg(feature = "cpi")]
+pub(crate) use seed_pool::__cpi_client_accounts_seed_pool;
+pub use swap::Swap;
+pub(crate) use swap::__client_accounts_swap;
+#[cfg(feature = "cpi")]
+pub(crate) use swap::__cpi_client_accounts_swap;
+pub use withdraw_locked_liquidity::WithdrawLockedLiquidity;
+pub(crate) use withdraw_locked_liquidity::__client_accounts_withdraw_locked_liquidity;
+#[cfg(feature = "cpi")]
+pub(crate) use withdraw_locked_liquidity::__cpi_client_accounts_withdraw_locked_liquidity;
+pub use withdraw_team_budget::WithdrawTeamBudget;
+pub(crate) use withdraw_team_budget::__client_accounts_withdraw_team_budget;
+#[cfg(feature = "cpi")]
+pub(crate) use withdraw_team_budget::__cpi_client_accounts_withdraw_team_budget;
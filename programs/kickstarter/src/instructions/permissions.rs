@@ -6,7 +6,7 @@ use ephemeral_rollups_sdk::consts::PERMISSION_PROGRAM_ID;
 use ephemeral_rollups_sdk::cpi::DelegateConfig;
 use ephemeral_rollups_sdk::ephem::commit_and_undelegate_accounts;
 
-use crate::constants::{SEED_KICKSTARTER, SEED_PRIVATE_STATE, SEED_QUOTE_VAULT};
+use crate::constants::{SEED_KICKSTARTER, SEED_PRIVATE_DEPOSIT, SEED_PRIVATE_STATE, SEED_QUOTE_VAULT};
 use crate::state::{Kickstarter, PrivateFundState};
 
 pub fn create_permission(
@@ -106,6 +106,7 @@ pub enum PermissionedAccountType {
     Kickstarter { admin: Pubkey, base_mint: Pubkey },
     QuoteVault { kickstarter: Pubkey },
     PrivateState { kickstarter: Pubkey },
+    PrivateDeposit { kickstarter: Pubkey, owner: Pubkey },
 }
 
 fn derive_seeds_from_account_type(account_type: &PermissionedAccountType) -> Vec<Vec<u8>> {
@@ -119,5 +120,8 @@ fn derive_seeds_from_account_type(account_type: &PermissionedAccountType) -> Vec
         PermissionedAccountType::PrivateState { kickstarter } => {
             vec![SEED_PRIVATE_STATE.as_bytes().to_vec(), kickstarter.to_bytes().to_vec()]
         }
+        PermissionedAccountType::PrivateDeposit { kickstarter, owner } => {
+            vec![SEED_PRIVATE_DEPOSIT.as_bytes().to_vec(), kickstarter.to_bytes().to_vec(), owner.to_bytes().to_vec()]
+        }
     }
 }
@@ -3,13 +3,14 @@ use anchor_spl::{
     token::{self, Token, Transfer},
     token_interface::TokenAccount as SplTokenAccount,
 };
-use sha2::{Digest, Sha256};
 
-use crate::state::{Kickstarter, KickstarterState, PrivateFundState};
+use crate::state::{CommitmentNullifier, Kickstarter, KickstarterState, PrivateFundState};
 use crate::error::ErrorCode;
-use crate::constants::{SEED_BASE_VAULT, SEED_PRIVATE_STATE};
+use crate::constants::{SEED_BASE_VAULT, SEED_NULLIFIER, SEED_PRIVATE_STATE};
+use crate::merkle_tree::{commitment_hash, nullifier_hash, verify_proof};
 
 #[derive(Accounts)]
+#[instruction(amount: u64, salt: [u8; 32])]
 pub struct PrivateClaim<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -21,6 +22,18 @@ pub struct PrivateClaim<'info> {
         has_one = kickstarter
     )]
     pub private_state: Account<'info, PrivateFundState>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + CommitmentNullifier::INIT_SPACE,
+        seeds = [
+            SEED_NULLIFIER.as_bytes(),
+            kickstarter.key().as_ref(),
+            nullifier_hash(&commitment_hash(&user.key(), amount, &salt)).as_ref()
+        ],
+        bump
+    )]
+    pub nullifier: Account<'info, CommitmentNullifier>,
     #[account(
         mut,
         address = kickstarter.base_vault,
@@ -31,58 +44,109 @@ pub struct PrivateClaim<'info> {
     #[account(mut)]
     pub user_base_account: InterfaceAccount<'info, SplTokenAccount>,
     pub token_program: Program<'info, Token>,
-    // pub system_program: Program<'info, System>
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(
     ctx: Context<PrivateClaim>,
     amount: u64,
-    salt: [u8; 32]
+    salt: [u8; 32],
+    leaf_index: u32,
+    proof: Vec<[u8; 32]>,
 ) -> Result<()> {
     let kickstarter = &ctx.accounts.kickstarter;
     let private_state = &ctx.accounts.private_state;
 
     require!(kickstarter.state == KickstarterState::Complete, ErrorCode::InvalidKickstarterState);
+    require!(kickstarter.is_pool_seeded(), ErrorCode::PoolNotSeeded);
 
-    let total_committed_snapshot = private_state.committed_amount;
-    require!(total_committed_snapshot > 0, ErrorCode::CommittedSnapshotMissing);
+    let commitment = commitment_hash(&ctx.accounts.user.key(), amount, &salt);
+    require!(
+        verify_proof(&private_state.commitments_root, commitment, leaf_index, &proof),
+        ErrorCode::InvalidCommitmentsRoot
+    );
 
-    // require!(kickstarter.private_investor_count > 0, ErrorCode::InvalidCommitmentsRoot);
+    let tokens_to_claim = record_private_claim(
+        kickstarter,
+        &mut ctx.accounts.nullifier,
+        ctx.bumps.nullifier,
+        commitment,
+        amount,
+    )?;
 
-    let base_tokens_to_user_u128 =
-        (amount as u128)
-        .checked_mul(kickstarter.total_base_tokens_for_investors as u128).unwrap()
-        .checked_div(total_committed_snapshot as u128).unwrap();
-    let base_tokens_to_user_u64 = u64::try_from(base_tokens_to_user_u128).unwrap();
+    let seeds = &[
+        b"kickstarter",
+        kickstarter.kickstarter_authority.as_ref(),
+        kickstarter.base_mint.as_ref(),
+        &[kickstarter.pda_bump]
+    ];
+    let signer = &[&seeds[..]];
 
-    if base_tokens_to_user_u64 > 0 {
-        let seeds = &[
-            b"kickstarter",
-            kickstarter.kickstarter_authority.as_ref(),
-            kickstarter.base_mint.as_ref(),
-            &[kickstarter.pda_bump]
-        ];
-        let signer = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.base_vault.to_account_info(),
+        to: ctx.accounts.user_base_account.to_account_info(),
+        authority: kickstarter.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer
+    );
+    token::transfer(cpi_ctx, tokens_to_claim)?;
 
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.base_vault.to_account_info(),
-            to: ctx.accounts.user_base_account.to_account_info(),
-            authority: kickstarter.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer
-        );
-        token::transfer(cpi_ctx, base_tokens_to_user_u64)?;
+    msg!(
+        "Private claim: user={}, amount={}, tokens={}",
+        ctx.accounts.user.key(),
+        amount,
+        tokens_to_claim
+    );
 
-        msg!(
-            "Private claim: user={}, amount={}, tokens={}",
-            ctx.accounts.user.key(),
-            amount,
-            base_tokens_to_user_u64
-        );
+    Ok(())
+}
+/// Entitlement, vesting and nullifier bookkeeping of a verified private commitment,
+/// shared by both private claim paths. Returns the base tokens to pay out now.
+pub(crate) fn record_private_claim(
+    kickstarter: &Account<Kickstarter>,
+    nullifier: &mut Account<CommitmentNullifier>,
+    nullifier_bump: u8,
+    commitment: [u8; 32],
+    amount: u64,
+) -> Result<u64> {
+    let total_committed_snapshot = kickstarter
+        .total_committed_at_completion
+        .ok_or(ErrorCode::CommittedSnapshotMissing)?;
+    require!(total_committed_snapshot > 0, ErrorCode::CommittedSnapshotMissing);
+
+    let entitlement_u128 = (amount as u128)
+        .checked_mul(kickstarter.total_base_tokens_for_investors as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(total_committed_snapshot as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let entitlement = u64::try_from(entitlement_u128).map_err(|_| ErrorCode::MathOverflow)?;
+    require!(entitlement > 0, ErrorCode::NothingToClaim);
+
+    // same schedule as public claims, tracked per commitment on its nullifier
+    require!(nullifier.claimed_amount < entitlement, ErrorCode::DoubleSpend);
+    if nullifier.kickstarter == Pubkey::default() {
+        nullifier.kickstarter = kickstarter.key();
+        nullifier.nullifier = nullifier_hash(&commitment);
+        nullifier.bump = nullifier_bump;
     }
 
-    Ok(())
-}
\ No newline at end of file
+    let vesting_start = kickstarter
+        .unix_timestamp_closed
+        .ok_or(ErrorCode::InvalidKickstarterState)?;
+    let vested_tokens = kickstarter.vested_base_tokens(
+        entitlement,
+        vesting_start,
+        Clock::get()?.unix_timestamp,
+    )?;
+    let tokens_to_claim = vested_tokens.saturating_sub(nullifier.claimed_amount);
+    require!(tokens_to_claim > 0, ErrorCode::NothingToClaim);
+    nullifier.claimed_amount = nullifier
+        .claimed_amount
+        .checked_add(tokens_to_claim)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    Ok(tokens_to_claim)
+}
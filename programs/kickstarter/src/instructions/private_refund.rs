@@ -3,13 +3,14 @@ use anchor_spl::{
     token::{self, Token, Transfer},
     token_interface::TokenAccount as SplTokenAccount,
 };
-use sha2::{Digest, Sha256};
 
-use crate::state::{Kickstarter, KickstarterState, PrivateFundState};
+use crate::state::{CommitmentNullifier, Kickstarter, KickstarterState, PrivateFundState};
 use crate::error::ErrorCode;
-use crate::constants::{SEED_PRIVATE_STATE, SEED_QUOTE_VAULT};
+use crate::constants::{SEED_NULLIFIER, SEED_PRIVATE_STATE, SEED_QUOTE_VAULT};
+use crate::merkle_tree::{commitment_hash, nullifier_hash, verify_proof};
 
 #[derive(Accounts)]
+#[instruction(amount: u64, salt: [u8; 32])]
 pub struct PrivateRefund<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -21,6 +22,18 @@ pub struct PrivateRefund<'info> {
         has_one = kickstarter
     )]
     pub private_state: Account<'info, PrivateFundState>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + CommitmentNullifier::INIT_SPACE,
+        seeds = [
+            SEED_NULLIFIER.as_bytes(),
+            kickstarter.key().as_ref(),
+            nullifier_hash(&commitment_hash(&user.key(), amount, &salt)).as_ref()
+        ],
+        bump
+    )]
+    pub nullifier: Account<'info, CommitmentNullifier>,
     #[account(
         mut,
         address = kickstarter.quote_vault,
@@ -31,41 +44,56 @@ pub struct PrivateRefund<'info> {
     #[account(mut)]
     pub user_quote_account: InterfaceAccount<'info, SplTokenAccount>,
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handler(
     ctx: Context<PrivateRefund>,
     amount: u64,
-    salt: [u8; 32]
+    salt: [u8; 32],
+    leaf_index: u32,
+    proof: Vec<[u8; 32]>,
 ) -> Result<()> {
     let kickstarter = &ctx.accounts.kickstarter;
     let private_state = &ctx.accounts.private_state;
 
-    require!(kickstarter.state == KickstarterState::Refunding, ErrorCode::InvalidKickstarterState);
-
-    let mut hasher = Sha256::new();
-    hasher.update(ctx.accounts.user.key().as_ref());
-    hasher.update(&amount.to_le_bytes());
-    hasher.update(&salt);
-    let commitment_hash: [u8; 32] = hasher.finalize().into();
-
-    // Verify commitment against current root
-    let mut expected_root = [0u8; 32];
-    if private_state.investor_count == 1 {
-        expected_root = commitment_hash;
-    } else {
-        let mut hasher = Sha256::new();
-        hasher.update(private_state.commitments_root);
-        hasher.update(commitment_hash);
-        expected_root = hasher.finalize().into();
-    }
+    let refund_amount = match kickstarter.state {
+        KickstarterState::Refunding => amount,
+        KickstarterState::Complete => {
+            let final_raise = kickstarter
+                .final_raise_amount
+                .ok_or(ErrorCode::FinalRaiseAmountMissing)?;
+            let total_committed_snapshot = kickstarter
+                .total_committed_at_completion
+                .ok_or(ErrorCode::CommittedSnapshotMissing)?;
+            require!(total_committed_snapshot > 0, ErrorCode::CommittedSnapshotMissing);
+            let accepted_u128 = (amount as u128)
+                .checked_mul(final_raise as u128)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_div(total_committed_snapshot as u128)
+                .ok_or(ErrorCode::MathOverflow)?;
+            let accepted = u64::try_from(accepted_u128).map_err(|_| ErrorCode::MathOverflow)?;
+            amount.checked_sub(accepted).ok_or(ErrorCode::MathOverflow)?
+        }
+        _ => return err!(ErrorCode::InvalidKickstarterState),
+    };
 
+    let commitment = commitment_hash(&ctx.accounts.user.key(), amount, &salt);
     require!(
-        private_state.commitments_root == expected_root,
+        verify_proof(&private_state.commitments_root, commitment, leaf_index, &proof),
         ErrorCode::InvalidCommitmentsRoot
     );
 
-    if amount > 0 {
+    let nullifier = &mut ctx.accounts.nullifier;
+    require!(!nullifier.refunded, ErrorCode::DoubleSpend);
+    if nullifier.kickstarter == Pubkey::default() {
+        nullifier.kickstarter = kickstarter.key();
+        nullifier.nullifier = nullifier_hash(&commitment);
+        nullifier.bump = ctx.bumps.nullifier;
+    }
+    nullifier.refunded = true;
+
+    if refund_amount > 0 {
         let seeds = &[
             b"kickstarter",
             kickstarter.kickstarter_authority.as_ref(),
@@ -84,12 +112,13 @@ pub fn handler(
             cpi_accounts,
             signer
         );
-        token::transfer(cpi_ctx, amount)?;
+        token::transfer(cpi_ctx, refund_amount)?;
 
         msg!(
-            "Private refund: user={}, amount={}",
+            "Private refund: user={}, amount={}, state={:?}",
             ctx.accounts.user.key(),
-            amount
+            refund_amount,
+            kickstarter.state
         );
     }
 
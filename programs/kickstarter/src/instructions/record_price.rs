@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::SEED_PRICE_OBSERVATIONS,
+    error::ErrorCode,
+    oracle,
+    state::{Kickstarter, KickstarterState, PriceObservations},
+};
+
+/// Permissionless crank: samples the configured price feed into the
+/// observations used for the performance package TWAP. Only the oracle is
+/// sampled, never the liquidity pool, whose spot price is cheap to move.
+#[derive(Accounts)]
+pub struct RecordPrice<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    pub kickstarter: Account<'info, Kickstarter>,
+    #[account(
+        init_if_needed,
+        payer = caller,
+        space = 8 + PriceObservations::INIT_SPACE,
+        seeds = [SEED_PRICE_OBSERVATIONS.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub price_observations: Box<Account<'info, PriceObservations>>,
+    /// CHECK: owner and layout are checked in oracle::load_price
+    #[account(address = kickstarter.price_feed @ ErrorCode::InvalidPriceFeed)]
+    pub price_feed: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RecordPrice>) -> Result<()> {
+    let kickstarter = &ctx.accounts.kickstarter;
+    require!(
+        kickstarter.state == KickstarterState::Complete,
+        ErrorCode::InvalidKickstarterState
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let oracle_price = oracle::load_price(&ctx.accounts.price_feed, &kickstarter.price_feed_id)?;
+    oracle_price.validate(
+        current_time,
+        kickstarter.max_price_age_seconds,
+        kickstarter.max_price_confidence_bps,
+    )?;
+    let price = oracle_price.to_token_price(kickstarter.token_price_decimals)?;
+
+    let price_observations = &mut ctx.accounts.price_observations;
+    price_observations.kickstarter = kickstarter.key();
+    price_observations.bump = ctx.bumps.price_observations;
+    price_observations.record(current_time, price, kickstarter.twap_window_seconds)?;
+
+    Ok(())
+}
@@ -64,7 +64,7 @@ pub fn handler(ctx: Context<Refund>) -> Result<()> {
 
     let refund_amount = total_refundable
         .checked_sub(funder_position.claimed_refund)
-        .unwrap();
+        .ok_or(ErrorCode::MathOverflow)?;
     if refund_amount > 0 {
         let seeds = &[
             b"kickstarter", 
@@ -86,7 +86,9 @@ pub fn handler(ctx: Context<Refund>) -> Result<()> {
         );
         token::transfer(cpi_ctx, refund_amount)?;
 
-        funder_position.claimed_refund = funder_position.claimed_refund.checked_add(refund_amount).unwrap();
+        funder_position.claimed_refund = funder_position.claimed_refund
+            .checked_add(refund_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
         emit!(RefundEvent {
             kickstarter: kickstarter.key(),
             user: ctx.accounts.user.key(),
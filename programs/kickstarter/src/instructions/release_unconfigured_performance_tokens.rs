@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, Transfer};
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    events::PerformanceTokensReleasedEvent,
+    error::ErrorCode,
+    state::{Kickstarter, KickstarterState, PerformanceTokenRelease},
+};
+
+#[derive(Accounts)]
+pub struct ReleaseUnconfiguredPerformanceTokens<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        constraint = kickstarter.kickstarter_authority == admin.key() @ ErrorCode::InvalidAdmin
+    )]
+    pub kickstarter: Box<Account<'info, Kickstarter>>,
+    #[account(mut, address = kickstarter.base_vault)]
+    pub base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, address = kickstarter.base_mint)]
+    pub base_mint: Box<Account<'info, Mint>>,
+    /// receives the unconfigured part of the performance pool with
+    /// `PerformanceTokenRelease::Treasury`, can be left out when burning it
+    #[account(
+        mut,
+        constraint = treasury_base_account.owner == kickstarter.treasury @ ErrorCode::InvalidTreasuryAccountOwner,
+        constraint = treasury_base_account.mint == kickstarter.base_mint @ ErrorCode::InvalidBaseMint,
+    )]
+    pub treasury_base_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(
+    ctx: Context<ReleaseUnconfiguredPerformanceTokens>,
+    release: PerformanceTokenRelease,
+) -> Result<()> {
+    let kickstarter = &ctx.accounts.kickstarter;
+    require!(
+        kickstarter.state == KickstarterState::Complete,
+        ErrorCode::InvalidKickstarterState
+    );
+    require!(
+        !kickstarter.unconfigured_performance_released,
+        ErrorCode::NothingToRelease
+    );
+
+    let amount = kickstarter
+        .calculated_performance_pool_tokens
+        .ok_or(ErrorCode::InvalidKickstarterState)?
+        .checked_sub(kickstarter.configured_performance_tokens)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(amount > 0, ErrorCode::NothingToRelease);
+
+    release_base_tokens(
+        kickstarter,
+        release,
+        amount,
+        ctx.accounts.base_vault.to_account_info(),
+        ctx.accounts.base_mint.to_account_info(),
+        ctx.accounts.treasury_base_account.as_ref().map(|account| account.to_account_info()),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+
+    ctx.accounts.kickstarter.unconfigured_performance_released = true;
+
+    emit!(PerformanceTokensReleasedEvent {
+        kickstarter: ctx.accounts.kickstarter.key(),
+        package_index: None,
+        amount,
+        burned: release == PerformanceTokenRelease::Burn,
+    });
+
+    Ok(())
+}
+
+/// Burns `amount` of base tokens from the vault, or moves them to the treasury.
+pub(crate) fn release_base_tokens<'info>(
+    kickstarter: &Account<'info, Kickstarter>,
+    release: PerformanceTokenRelease,
+    amount: u64,
+    base_vault: AccountInfo<'info>,
+    base_mint: AccountInfo<'info>,
+    treasury_base_account: Option<AccountInfo<'info>>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let seeds = &[
+        b"kickstarter",
+        kickstarter.kickstarter_authority.as_ref(),
+        kickstarter.base_mint.as_ref(),
+        &[kickstarter.pda_bump]
+    ];
+    let signer = &[&seeds[..]];
+
+    match release {
+        PerformanceTokenRelease::Burn => {
+            let cpi_ctx = CpiContext::new_with_signer(
+                token_program,
+                Burn {
+                    mint: base_mint,
+                    from: base_vault,
+                    authority: kickstarter.to_account_info(),
+                },
+                signer,
+            );
+            token::burn(cpi_ctx, amount)
+        }
+        PerformanceTokenRelease::Treasury => {
+            let treasury_base_account =
+                treasury_base_account.ok_or(ErrorCode::InvalidTreasuryAccountOwner)?;
+            let cpi_ctx = CpiContext::new_with_signer(
+                token_program,
+                Transfer {
+                    from: base_vault,
+                    to: treasury_base_account,
+                    authority: kickstarter.to_account_info(),
+                },
+                signer,
+            );
+            token::transfer(cpi_ctx, amount)
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Mint, MintTo, Transfer, TokenAccount as SplTokenAccount};
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    amm,
+    events::{LiquidityLockedEvent, LiquidityPoolSeededEvent},
+    state::{Kickstarter, KickstarterState, LiquidityPool},
+};
+use crate::error::ErrorCode;
+use crate::constants::{
+    POOL_FEE_BPS,
+    SEED_LIQUIDITY_POOL,
+    SEED_LP_MINT,
+    SEED_LP_VAULT,
+    SEED_POOL_BASE_VAULT,
+    SEED_POOL_QUOTE_VAULT,
+    SEED_TREASURY_VAULT,
+};
+
+/// Second step of a successful completion: creates the pool and treasury vault,
+/// moves the liquidity and the rest of the raise into them and locks the LP
+/// tokens. Kept apart from complete so a refunding raise creates none of these.
+/// Anyone can run it, and claims and the team budget stay closed until someone does.
+#[derive(Accounts)]
+pub struct SeedPool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub kickstarter: Box<Account<'info, Kickstarter>>,
+
+    #[account(
+        mut,
+        address = kickstarter.quote_vault
+    )]
+    pub quote_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = kickstarter.base_vault
+    )]
+    pub base_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = kickstarter.base_mint)]
+    pub base_mint: Box<Account<'info, Mint>>,
+
+    #[account(address = kickstarter.quote_mint)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LiquidityPool::INIT_SPACE,
+        seeds = [SEED_LIQUIDITY_POOL.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = base_mint,
+        token::authority = kickstarter,
+        seeds = [SEED_POOL_BASE_VAULT.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub pool_base_vault: Box<Account<'info, SplTokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = quote_mint,
+        token::authority = kickstarter,
+        seeds = [SEED_POOL_QUOTE_VAULT.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub pool_quote_vault: Box<Account<'info, SplTokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = base_mint.decimals,
+        mint::authority = kickstarter,
+        seeds = [SEED_LP_MINT.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = lp_mint,
+        token::authority = kickstarter,
+        seeds = [SEED_LP_VAULT.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub lp_vault: Box<Account<'info, SplTokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = quote_mint,
+        token::authority = kickstarter,
+        seeds = [SEED_TREASURY_VAULT.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub treasury_vault: Box<Account<'info, SplTokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SeedPool>) -> Result<()> {
+    require!(
+        ctx.accounts.kickstarter.state == KickstarterState::Complete,
+        ErrorCode::InvalidKickstarterState
+    );
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    liquidity_pool.kickstarter = ctx.accounts.kickstarter.key();
+    liquidity_pool.base_vault = ctx.accounts.pool_base_vault.key();
+    liquidity_pool.quote_vault = ctx.accounts.pool_quote_vault.key();
+    liquidity_pool.lp_mint = ctx.accounts.lp_mint.key();
+    liquidity_pool.lp_vault = ctx.accounts.lp_vault.key();
+    liquidity_pool.fee_bps = POOL_FEE_BPS;
+    liquidity_pool.bump = ctx.bumps.liquidity_pool;
+    ctx.accounts.kickstarter.treasury_vault = ctx.accounts.treasury_vault.key();
+
+    let kickstarter = &ctx.accounts.kickstarter;
+    let final_raise_amount = kickstarter
+        .final_raise_amount
+        .ok_or(ErrorCode::FinalRaiseAmountMissing)?;
+    let liquidity_amount = kickstarter
+        .calculated_liquidity_amount
+        .ok_or(ErrorCode::InvalidKickstarterState)?;
+    let base_liquidity_amount = kickstarter
+        .calculated_base_tokens_for_liquidity
+        .ok_or(ErrorCode::InvalidKickstarterState)?;
+    let treasury_amount = final_raise_amount
+        .checked_sub(liquidity_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let seeds = &[
+        b"kickstarter",
+        kickstarter.kickstarter_authority.as_ref(),
+        kickstarter.base_mint.as_ref(),
+        &[kickstarter.pda_bump]
+    ];
+    let signer = &[&seeds[..]];
+
+    if liquidity_amount > 0 {
+        let cpi_ctx_liquidity = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.quote_vault.to_account_info(),
+                to: ctx.accounts.pool_quote_vault.to_account_info(),
+                authority: kickstarter.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(cpi_ctx_liquidity, liquidity_amount)?;
+    }
+
+    if base_liquidity_amount > 0 {
+        let cpi_ctx_base_liquidity = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.base_vault.to_account_info(),
+                to: ctx.accounts.pool_base_vault.to_account_info(),
+                authority: kickstarter.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(cpi_ctx_base_liquidity, base_liquidity_amount)?;
+    }
+
+    if treasury_amount > 0 {
+        let cpi_ctx_treasury = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.quote_vault.to_account_info(),
+                to: ctx.accounts.treasury_vault.to_account_info(),
+                authority: kickstarter.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(cpi_ctx_treasury, treasury_amount)?;
+    }
+
+    let mut lp_amount = 0;
+    if liquidity_amount > 0 && base_liquidity_amount > 0 {
+        lp_amount = amm::initial_lp_amount(base_liquidity_amount, liquidity_amount)?;
+        let cpi_ctx_mint_lp = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.lp_vault.to_account_info(),
+                authority: kickstarter.to_account_info(),
+            },
+            signer,
+        );
+        token::mint_to(cpi_ctx_mint_lp, lp_amount)?;
+
+        emit!(LiquidityPoolSeededEvent {
+            kickstarter: kickstarter.key(),
+            pool: ctx.accounts.liquidity_pool.key(),
+            base_amount: base_liquidity_amount,
+            quote_amount: liquidity_amount,
+            lp_amount,
+        });
+    }
+
+    // also set for a one-sided pool without LP tokens, its reserve is withdrawn
+    // the same way
+    let unlock_timestamp = current_time
+        .checked_add(ctx.accounts.kickstarter.liquidity_lock_seconds as i64)
+        .ok_or(ErrorCode::MathOverflow)?;
+    ctx.accounts.kickstarter.liquidity_unlock_timestamp = Some(unlock_timestamp);
+    emit!(LiquidityLockedEvent {
+        kickstarter: ctx.accounts.kickstarter.key(),
+        lp_vault: ctx.accounts.lp_vault.key(),
+        lp_amount,
+        unlock_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::HardCapUpdatedEvent,
+    error::ErrorCode,
+    constants::SEED_PRIVATE_STATE,
+    state::{Kickstarter, KickstarterState, PrivateFundState},
+};
+
+#[derive(Accounts)]
+pub struct SetHardCap<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        constraint = kickstarter.kickstarter_authority == admin.key() @ ErrorCode::InvalidAdmin
+    )]
+    pub kickstarter: Account<'info, Kickstarter>,
+    /// CHECK: the cap can't drop below its committed amount, so the cap stays
+    /// fixed while the account is delegated to the rollup
+    #[account(
+        seeds = [SEED_PRIVATE_STATE.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub private_state: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SetHardCap>, new_hard_cap: u64, close_on_hard_cap: bool) -> Result<()> {
+    let kickstarter = &mut ctx.accounts.kickstarter;
+    require!(
+        matches!(
+            kickstarter.state,
+            KickstarterState::Initialized | KickstarterState::Live
+        ),
+        ErrorCode::InvalidKickstarterState
+    );
+
+    require!(new_hard_cap >= kickstarter.minimum_raise_amount, ErrorCode::InvalidHardCap);
+    let private_committed_amount =
+        PrivateFundState::base_layer_committed_amount(&ctx.accounts.private_state)?;
+    require!(
+        new_hard_cap >= kickstarter.combined_committed_amount(private_committed_amount)?,
+        ErrorCode::InvalidHardCap
+    );
+
+    kickstarter.hard_cap = new_hard_cap;
+    kickstarter.close_on_hard_cap = close_on_hard_cap;
+
+    emit!(HardCapUpdatedEvent {
+        kickstarter: kickstarter.key(),
+        new_hard_cap,
+        close_on_hard_cap,
+    });
+
+    Ok(())
+}
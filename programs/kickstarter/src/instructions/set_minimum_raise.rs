@@ -29,8 +29,9 @@ pub fn handler(ctx: Context<SetMinimumRaise>, new_minimum: u64) -> Result<()> {
         ErrorCode::InvalidKickstarterState
     );
 
+    require!(new_minimum <= kickstarter.hard_cap, ErrorCode::InvalidMinimumRaiseAmount);
+
     kickstarter.minimum_raise_amount = new_minimum;
-    kickstarter.hard_cap = u64::MAX;
 
     emit!(MinimumRaiseUpdatedEvent {
         kickstarter: kickstarter.key(),
@@ -19,8 +19,14 @@ pub fn handler(ctx: Context<StartKickstarter>) -> Result<()> {
     
     require!(kickstarter.state == KickstarterState::Initialized, ErrorCode::InvalidKickstarterState);
     
+    let now = Clock::get()?.unix_timestamp;
     kickstarter.state = KickstarterState::Live;
-    kickstarter.unix_timestamp_started = Some(Clock::get()?.unix_timestamp);
+    kickstarter.unix_timestamp_started = Some(now);
+    // funding deadline while Live, replaced by the actual close time on completion
+    kickstarter.unix_timestamp_closed = Some(
+        now.checked_add(kickstarter.seconds_for_launch as i64)
+            .ok_or(ErrorCode::MathOverflow)?,
+    );
 
     Ok(())
 }
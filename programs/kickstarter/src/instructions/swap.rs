@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{self, Token, Transfer},
+    token_interface::TokenAccount as SplTokenAccount,
+};
+
+use crate::{
+    amm,
+    constants::SEED_LIQUIDITY_POOL,
+    events::SwapEvent,
+    error::ErrorCode,
+    state::{Kickstarter, KickstarterState, LiquidityPool},
+};
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub kickstarter: Box<Account<'info, Kickstarter>>,
+    #[account(
+        seeds = [SEED_LIQUIDITY_POOL.as_bytes(), kickstarter.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = kickstarter
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+    #[account(mut, address = liquidity_pool.base_vault)]
+    pub pool_base_vault: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(mut, address = liquidity_pool.quote_vault)]
+    pub pool_quote_vault: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(
+        mut,
+        constraint = user_base_account.mint == kickstarter.base_mint @ ErrorCode::InvalidBaseMint,
+    )]
+    pub user_base_account: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(
+        mut,
+        constraint = user_quote_account.mint == kickstarter.quote_mint @ ErrorCode::InvalidQuoteMint,
+    )]
+    pub user_quote_account: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(
+    ctx: Context<Swap>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    base_to_quote: bool,
+) -> Result<()> {
+    let kickstarter = &ctx.accounts.kickstarter;
+    require!(
+        kickstarter.state == KickstarterState::Complete,
+        ErrorCode::InvalidKickstarterState
+    );
+
+    let (user_source, pool_destination, pool_source, user_destination) = if base_to_quote {
+        (
+            &ctx.accounts.user_base_account,
+            &ctx.accounts.pool_base_vault,
+            &ctx.accounts.pool_quote_vault,
+            &ctx.accounts.user_quote_account,
+        )
+    } else {
+        (
+            &ctx.accounts.user_quote_account,
+            &ctx.accounts.pool_quote_vault,
+            &ctx.accounts.pool_base_vault,
+            &ctx.accounts.user_base_account,
+        )
+    };
+
+    let amount_out = amm::swap_output(
+        amount_in,
+        pool_destination.amount,
+        pool_source.amount,
+        ctx.accounts.liquidity_pool.fee_bps,
+    )?;
+    require!(amount_out > 0, ErrorCode::InsufficientLiquidity);
+    require!(amount_out >= minimum_amount_out, ErrorCode::SlippageExceeded);
+
+    let cpi_ctx_in = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: user_source.to_account_info(),
+            to: pool_destination.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(cpi_ctx_in, amount_in)?;
+
+    let seeds = &[
+        b"kickstarter",
+        kickstarter.kickstarter_authority.as_ref(),
+        kickstarter.base_mint.as_ref(),
+        &[kickstarter.pda_bump]
+    ];
+    let signer = &[&seeds[..]];
+    let cpi_ctx_out = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: pool_source.to_account_info(),
+            to: user_destination.to_account_info(),
+            authority: kickstarter.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx_out, amount_out)?;
+
+    emit!(SwapEvent {
+        kickstarter: kickstarter.key(),
+        user: ctx.accounts.user.key(),
+        base_to_quote,
+        amount_in,
+        amount_out,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{self, Token, Transfer},
+    token_interface::TokenAccount as SplTokenAccount,
+};
+
+use crate::{
+    events::RefundsTriggeredEvent,
+    state::{Kickstarter, KickstarterState, PrivateFundState},
+};
+use crate::error::ErrorCode;
+use crate::constants::{
+    COMPLETION_GRACE_PERIOD_SECONDS, SEED_PRIVATE_QUOTE_VAULT, SEED_PRIVATE_STATE, SEED_QUOTE_VAULT,
+};
+
+/// Permissionless: once the deadline has passed, anyone can move a raise that
+/// missed the minimum, or that the admin never completed within the grace
+/// period, into `Refunding`.
+#[derive(Accounts)]
+pub struct TriggerRefunds<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    #[account(mut)]
+    pub kickstarter: Box<Account<'info, Kickstarter>>,
+    #[account(
+        mut,
+        seeds = [SEED_PRIVATE_STATE.as_bytes(), kickstarter.key().as_ref()],
+        bump,
+        has_one = kickstarter
+    )]
+    pub private_state: Box<Account<'info, PrivateFundState>>,
+    #[account(
+        mut,
+        address = kickstarter.private_quote_vault,
+        seeds = [SEED_PRIVATE_QUOTE_VAULT.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub private_quote_vault: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(
+        mut,
+        address = kickstarter.quote_vault,
+        seeds = [SEED_QUOTE_VAULT.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<TriggerRefunds>) -> Result<()> {
+    let kickstarter = &mut ctx.accounts.kickstarter;
+    let private_state = &mut ctx.accounts.private_state;
+
+    require!(kickstarter.state == KickstarterState::Live, ErrorCode::InvalidKickstarterState);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let deadline = kickstarter
+        .unix_timestamp_closed
+        .ok_or(ErrorCode::InvalidKickstarterState)?;
+    if current_time < deadline {
+        return err!(ErrorCode::TooEarlyToCompleteKickstarter);
+    }
+
+    let total_committed = kickstarter.total_committed_amount
+        .checked_add(private_state.committed_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let grace_period_end = deadline
+        .checked_add(COMPLETION_GRACE_PERIOD_SECONDS)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        total_committed < kickstarter.minimum_raise_amount || current_time >= grace_period_end,
+        ErrorCode::RaiseCanStillBeCompleted
+    );
+
+    // the private round may never have been finalized, bring its escrow next to
+    // the public funds so private_refund can pay it out
+    let unsettled_amount = private_state
+        .committed_amount
+        .checked_sub(private_state.settled_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if unsettled_amount > 0 {
+        let seeds = &[
+            b"kickstarter",
+            kickstarter.kickstarter_authority.as_ref(),
+            kickstarter.base_mint.as_ref(),
+            &[kickstarter.pda_bump]
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.private_quote_vault.to_account_info(),
+                to: ctx.accounts.quote_vault.to_account_info(),
+                authority: kickstarter.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(cpi_ctx, unsettled_amount)?;
+        private_state.settled_amount = private_state.committed_amount;
+    }
+
+    kickstarter.state = KickstarterState::Refunding;
+    kickstarter.is_private_round_active = false;
+    kickstarter.final_raise_amount = None;
+    kickstarter.total_committed_at_completion = None;
+    kickstarter.unix_timestamp_closed = Some(current_time);
+
+    emit!(RefundsTriggeredEvent {
+        kickstarter: kickstarter.key(),
+        total_committed,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -1,12 +1,13 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    constants::MAX_PERFORMANCE_PACKAGES,
+    constants::{SEED_PERFORMANCE_PACKAGE, SEED_PRICE_OBSERVATIONS},
     error::ErrorCode,
-    state::{Kickstarter, KickstarterState},
+    state::{Kickstarter, KickstarterState, PerformancePackage, PriceObservations},
 };
 
 #[derive(Accounts)]
+#[instruction(index: u8)]
 pub struct UnlockPerformancePackage<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -15,16 +16,30 @@ pub struct UnlockPerformancePackage<'info> {
         constraint = kickstarter.kickstarter_authority == admin.key() @ ErrorCode::InvalidAdmin,
     )]
     pub kickstarter: Account<'info, Kickstarter>,
+    #[account(
+        seeds = [SEED_PRICE_OBSERVATIONS.as_bytes(), kickstarter.key().as_ref()],
+        bump = price_observations.bump,
+        has_one = kickstarter
+    )]
+    pub price_observations: Box<Account<'info, PriceObservations>>,
+    #[account(
+        mut,
+        seeds = [SEED_PERFORMANCE_PACKAGE.as_bytes(), kickstarter.key().as_ref(), &[index]],
+        bump = performance_package.bump,
+        has_one = kickstarter
+    )]
+    pub performance_package: Account<'info, PerformancePackage>,
+    /// package at index - 1, only needed when performance_package depends on it
+    #[account(
+        seeds = [SEED_PERFORMANCE_PACKAGE.as_bytes(), kickstarter.key().as_ref(), &[index.wrapping_sub(1)]],
+        bump = previous_package.bump,
+        has_one = kickstarter
+    )]
+    pub previous_package: Option<Account<'info, PerformancePackage>>,
 }
 
-pub fn handler(ctx: Context<UnlockPerformancePackage>, index: u8, current_price: u64) -> Result<()> {
-    let index_usize = index as usize;
-    require!(
-        index_usize < MAX_PERFORMANCE_PACKAGES,
-        ErrorCode::InvalidPerformancePackageIndex
-    );
-
-    let kickstarter = &mut ctx.accounts.kickstarter;
+pub fn handler(ctx: Context<UnlockPerformancePackage>, _index: u8) -> Result<()> {
+    let kickstarter = &ctx.accounts.kickstarter;
     require!(
         kickstarter.state == KickstarterState::Complete,
         ErrorCode::InvalidKickstarterState
@@ -37,61 +52,59 @@ pub fn handler(ctx: Context<UnlockPerformancePackage>, index: u8, current_price:
     let current_time = Clock::get()?.unix_timestamp;
     let unlock_delay = kickstarter.package_unlock_delay_seconds;
 
-    if index_usize == 0 {
-        let earliest_unlock = complete_time
-            .checked_add(unlock_delay)
-            .ok_or(ErrorCode::MathOverflow)?;
-        require!(
-            current_time >= earliest_unlock,
-            ErrorCode::TooEarlyToUnlockPackage
-        );
-    } else {
-        let prev_package = &kickstarter.performance_packages[index_usize - 1];
+    let package = &ctx.accounts.performance_package;
+    require!(
+        !package.is_unlocked,
+        ErrorCode::PerformancePackageAlreadyUnlocked
+    );
+    require!(
+        !package.is_expired && !kickstarter.performance_packages_expired(current_time)?,
+        ErrorCode::PerformancePackageExpired
+    );
+
+    let delay_start = if package.depends_on_previous {
+        let prev_package = ctx
+            .accounts
+            .previous_package
+            .as_ref()
+            .ok_or(ErrorCode::PreviousPackageNotUnlocked)?;
         require!(
             prev_package.is_unlocked,
             ErrorCode::PreviousPackageNotUnlocked
         );
-        
-        let prev_unlock_time = prev_package
+        prev_package
             .unlocked_at
-            .ok_or(ErrorCode::PreviousPackageNotUnlocked)?;
-        
-        let earliest_unlock = prev_unlock_time
-            .checked_add(unlock_delay)
-            .ok_or(ErrorCode::MathOverflow)?;
-        require!(
-            current_time >= earliest_unlock,
-            ErrorCode::TooEarlyToUnlockPackage
-        );
-    }
+            .ok_or(ErrorCode::PreviousPackageNotUnlocked)?
+    } else {
+        complete_time
+    };
+
+    let earliest_unlock = delay_start
+        .checked_add(unlock_delay)
+        .ok_or(ErrorCode::MathOverflow)?;
+    require!(
+        current_time >= earliest_unlock,
+        ErrorCode::TooEarlyToUnlockPackage
+    );
 
     let initial_price = kickstarter
         .initial_token_price
         .ok_or(ErrorCode::InitialTokenPriceNotSet)?;
 
-    let package = &kickstarter.performance_packages[index_usize];
-    require!(
-        package.is_configured,
-        ErrorCode::PerformancePackageNotConfigured
-    );
-    require!(
-        !package.is_unlocked,
-        ErrorCode::PerformancePackageAlreadyUnlocked
-    );
-
-    let target_price_u128 = (initial_price as u128)
-        .checked_mul(package.multiplier as u128)
-        .ok_or(ErrorCode::MathOverflow)?;
-    let target_price = u64::try_from(target_price_u128).map_err(|_| ErrorCode::MathOverflow)?;
+    let target_price = package.target_price(initial_price)?;
 
+    let average_price = ctx
+        .accounts
+        .price_observations
+        .twap(kickstarter.twap_window_seconds, current_time)?;
     require!(
-        current_price >= target_price,
+        average_price >= target_price,
         ErrorCode::PriceTargetNotReached
     );
 
-    let package_mut = &mut kickstarter.performance_packages[index_usize];
-    package_mut.is_unlocked = true;
-    package_mut.unlocked_at = Some(current_time);
+    let package = &mut ctx.accounts.performance_package;
+    package.is_unlocked = true;
+    package.unlocked_at = Some(current_time);
 
     Ok(())
 }
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{self, Token, Transfer},
+    token_interface::TokenAccount as SplTokenAccount,
+};
+
+use crate::events::WithdrawEvent;
+use crate::state::{FunderPosition, Kickstarter, KickstarterState};
+use crate::error::ErrorCode;
+
+use crate::constants::SEED_QUOTE_VAULT;
+
+#[derive(Accounts)]
+pub struct WithdrawCommitment<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    #[account(mut)]
+    pub kickstarter: Account<'info, Kickstarter>,
+    #[account(
+        mut,
+        seeds = [b"funder_position", kickstarter.key().as_ref(), funder.key().as_ref()],
+        bump = funder_position.bump
+    )]
+    pub funder_position: Account<'info, FunderPosition>,
+    #[account(mut)]
+    pub funder_quote_account: InterfaceAccount<'info, SplTokenAccount>,
+    #[account(
+        mut,
+        address = kickstarter.quote_vault,
+        seeds = [SEED_QUOTE_VAULT.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub quote_vault: InterfaceAccount<'info, SplTokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<WithdrawCommitment>, amount: u64) -> Result<()> {
+    let kickstarter = &mut ctx.accounts.kickstarter;
+    let funder_position = &mut ctx.accounts.funder_position;
+
+    require!(kickstarter.state == KickstarterState::Live, ErrorCode::InvalidKickstarterState);
+
+    // late withdrawals would let a whale pull out right before completion
+    let deadline = kickstarter
+        .unix_timestamp_closed
+        .ok_or(ErrorCode::InvalidKickstarterState)?;
+    let cutoff = deadline
+        .checked_sub(kickstarter.withdrawal_cutoff_seconds as i64)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if Clock::get()?.unix_timestamp >= cutoff {
+        return err!(ErrorCode::WithdrawalWindowClosed);
+    }
+
+    require!(amount > 0, ErrorCode::InsufficientCommitment);
+    require!(amount <= funder_position.committed_amount, ErrorCode::InsufficientCommitment);
+
+    funder_position.committed_amount = funder_position
+        .committed_amount
+        .checked_sub(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    kickstarter.total_committed_amount = kickstarter
+        .total_committed_amount
+        .checked_sub(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let seeds = &[
+        b"kickstarter",
+        kickstarter.kickstarter_authority.as_ref(),
+        kickstarter.base_mint.as_ref(),
+        &[kickstarter.pda_bump]
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.quote_vault.to_account_info(),
+        to: ctx.accounts.funder_quote_account.to_account_info(),
+        authority: kickstarter.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    emit!(WithdrawEvent {
+        kickstarter: kickstarter.key(),
+        funder: ctx.accounts.funder.key(),
+        amount,
+        total_committed: kickstarter.total_committed_amount,
+    });
+
+    Ok(())
+}
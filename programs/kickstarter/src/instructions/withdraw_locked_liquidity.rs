@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, Transfer};
+use anchor_spl::token_interface::TokenAccount as SplTokenAccount;
+
+use crate::{
+    amm,
+    constants::SEED_LIQUIDITY_POOL,
+    events::LockedLiquidityWithdrawnEvent,
+    error::ErrorCode,
+    state::{Kickstarter, LiquidityPool},
+};
+
+/// Burns the LP tokens held since completion and pays the pool share out to the
+/// authority, once `liquidity_unlock_timestamp` has passed.
+#[derive(Accounts)]
+pub struct WithdrawLockedLiquidity<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        constraint = kickstarter.kickstarter_authority == admin.key() @ ErrorCode::InvalidAdmin
+    )]
+    pub kickstarter: Box<Account<'info, Kickstarter>>,
+    #[account(
+        seeds = [SEED_LIQUIDITY_POOL.as_bytes(), kickstarter.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = kickstarter
+    )]
+    pub liquidity_pool: Box<Account<'info, LiquidityPool>>,
+    #[account(mut, address = liquidity_pool.base_vault)]
+    pub pool_base_vault: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(mut, address = liquidity_pool.quote_vault)]
+    pub pool_quote_vault: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(mut, address = liquidity_pool.lp_mint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    #[account(mut, address = liquidity_pool.lp_vault)]
+    pub lp_vault: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(
+        mut,
+        constraint = admin_base_account.mint == kickstarter.base_mint @ ErrorCode::InvalidBaseMint,
+    )]
+    pub admin_base_account: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    #[account(
+        mut,
+        constraint = admin_quote_account.mint == kickstarter.quote_mint @ ErrorCode::InvalidQuoteMint,
+    )]
+    pub admin_quote_account: Box<InterfaceAccount<'info, SplTokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<WithdrawLockedLiquidity>) -> Result<()> {
+    let kickstarter = &ctx.accounts.kickstarter;
+    let unlock_timestamp = kickstarter
+        .liquidity_unlock_timestamp
+        .ok_or(ErrorCode::InvalidKickstarterState)?;
+    require!(
+        Clock::get()?.unix_timestamp >= unlock_timestamp,
+        ErrorCode::LiquidityStillLocked
+    );
+
+    let lp_amount = ctx.accounts.lp_vault.amount;
+    // a one-sided seed minted no LP tokens, its reserve goes back as is
+    let (base_amount, quote_amount) = if lp_amount == 0 && ctx.accounts.lp_mint.supply == 0 {
+        (ctx.accounts.pool_base_vault.amount, ctx.accounts.pool_quote_vault.amount)
+    } else {
+        amm::redeem_amounts(
+            lp_amount,
+            ctx.accounts.lp_mint.supply,
+            ctx.accounts.pool_base_vault.amount,
+            ctx.accounts.pool_quote_vault.amount,
+        )?
+    };
+
+    let seeds = &[
+        b"kickstarter",
+        kickstarter.kickstarter_authority.as_ref(),
+        kickstarter.base_mint.as_ref(),
+        &[kickstarter.pda_bump]
+    ];
+    let signer = &[&seeds[..]];
+
+    if lp_amount > 0 {
+        let cpi_ctx_burn = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.lp_vault.to_account_info(),
+                authority: kickstarter.to_account_info(),
+            },
+            signer,
+        );
+        token::burn(cpi_ctx_burn, lp_amount)?;
+    }
+
+    let cpi_ctx_base = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.pool_base_vault.to_account_info(),
+            to: ctx.accounts.admin_base_account.to_account_info(),
+            authority: kickstarter.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx_base, base_amount)?;
+
+    let cpi_ctx_quote = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.pool_quote_vault.to_account_info(),
+            to: ctx.accounts.admin_quote_account.to_account_info(),
+            authority: kickstarter.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx_quote, quote_amount)?;
+
+    emit!(LockedLiquidityWithdrawnEvent {
+        kickstarter: kickstarter.key(),
+        lp_amount,
+        base_amount,
+        quote_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{self, Token, Transfer},
+    token_interface::TokenAccount as SplTokenAccount,
+};
+
+use crate::state::{Kickstarter, PrivateDeposit};
+use crate::error::ErrorCode;
+use crate::constants::{SEED_PRIVATE_DEPOSIT, SEED_PRIVATE_QUOTE_VAULT};
+
+#[derive(Accounts)]
+pub struct WithdrawPrivateDeposit<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub kickstarter: Account<'info, Kickstarter>,
+    #[account(
+        mut,
+        seeds = [SEED_PRIVATE_DEPOSIT.as_bytes(), kickstarter.key().as_ref(), funder.key().as_ref()],
+        bump = private_deposit.bump,
+        has_one = kickstarter
+    )]
+    pub private_deposit: Account<'info, PrivateDeposit>,
+    #[account(mut)]
+    pub funder_quote_account: InterfaceAccount<'info, SplTokenAccount>,
+    #[account(
+        mut,
+        address = kickstarter.private_quote_vault,
+        seeds = [SEED_PRIVATE_QUOTE_VAULT.as_bytes(), kickstarter.key().as_ref()],
+        bump
+    )]
+    pub private_quote_vault: InterfaceAccount<'info, SplTokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns the part of a private deposit that never went into a commitment.
+pub fn handler(ctx: Context<WithdrawPrivateDeposit>) -> Result<()> {
+    let kickstarter = &ctx.accounts.kickstarter;
+    let private_deposit = &mut ctx.accounts.private_deposit;
+
+    // while the round is active the deposit lives in the rollup
+    require!(!kickstarter.is_private_round_active, ErrorCode::InvalidKickstarterState);
+
+    let amount = private_deposit.balance;
+    if amount > 0 {
+        let seeds = &[
+            b"kickstarter",
+            kickstarter.kickstarter_authority.as_ref(),
+            kickstarter.base_mint.as_ref(),
+            &[kickstarter.pda_bump]
+        ];
+        let signer = &[&seeds[..]];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.private_quote_vault.to_account_info(),
+            to: ctx.accounts.funder_quote_account.to_account_info(),
+            authority: kickstarter.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        private_deposit.balance = 0;
+
+        msg!("Private deposit withdrawn: amount={}", amount);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, Transfer};
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    events::TeamBudgetWithdrawnEvent,
+    state::{Kickstarter, KickstarterState},
+    error::ErrorCode,
+};
+
+/// Pays the team out of the treasury vault, at most monthly_team_spending_usdc
+/// per elapsed period since completion.
+#[derive(Accounts)]
+pub struct WithdrawTeamBudget<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        constraint = kickstarter.kickstarter_authority == admin.key() @ ErrorCode::InvalidAdmin,
+        constraint = kickstarter.is_pool_seeded() @ ErrorCode::PoolNotSeeded
+    )]
+    pub kickstarter: Box<Account<'info, Kickstarter>>,
+    #[account(mut, address = kickstarter.treasury_vault)]
+    pub treasury_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == kickstarter.treasury @ ErrorCode::InvalidTreasuryAccountOwner,
+        constraint = treasury_token_account.mint == kickstarter.quote_mint @ ErrorCode::InvalidQuoteMint,
+    )]
+    pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<WithdrawTeamBudget>, amount: u64) -> Result<()> {
+    require!(
+        ctx.accounts.kickstarter.state == KickstarterState::Complete,
+        ErrorCode::InvalidKickstarterState
+    );
+    require!(amount > 0, ErrorCode::NothingToClaim);
+
+    let kickstarter = &mut ctx.accounts.kickstarter;
+    kickstarter.accrue_team_budget(Clock::get()?.unix_timestamp)?;
+    require!(amount <= kickstarter.team_budget_available, ErrorCode::TeamBudgetExceeded);
+    kickstarter.team_budget_available -= amount;
+
+    let seeds = &[
+        b"kickstarter",
+        kickstarter.kickstarter_authority.as_ref(),
+        kickstarter.base_mint.as_ref(),
+        &[kickstarter.pda_bump]
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.treasury_vault.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: kickstarter.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(cpi_ctx, amount)?;
+
+    emit!(TeamBudgetWithdrawnEvent {
+        kickstarter: kickstarter.key(),
+        amount,
+        available_after: kickstarter.team_budget_available,
+        accrued_until: kickstarter.team_budget_accrued_until.unwrap_or_default(),
+    });
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
+pub mod amm;
+pub mod attestation;
 pub mod constants;
 pub mod error;
 pub mod events;
 pub mod instructions;
+pub mod merkle_tree;
+pub mod oracle;
 pub mod state;
 
 use anchor_lang::prelude::*;
@@ -20,6 +24,7 @@ declare_id!("DAqDXj2S1ipsGpe4eAi7AXBUT4MPd61DMDrq7QkvKUoY");
 pub mod kickstarter {
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_kickstarter(
         ctx: Context<InitializeKickstarter>,
         minimum_raise_amount: u64,
@@ -32,6 +37,22 @@ pub mod kickstarter {
         token_symbol: String,
         token_description: String,
         token_image_url: String,
+        private_round_attester: Pubkey,
+        hard_cap: u64,
+        close_on_hard_cap: bool,
+        withdrawal_cutoff_seconds: u32,
+        vesting_tge_bps: u16,
+        vesting_cliff_seconds: u32,
+        vesting_duration_seconds: u32,
+        price_feed: Pubkey,
+        price_feed_id: [u8; 32],
+        max_price_age_seconds: u32,
+        max_price_confidence_bps: u16,
+        twap_window_seconds: u32,
+        performance_package_expiry_seconds: u32,
+        liquidity_lock_seconds: u32,
+        liquidity_bps: u16,
+        base_tokens_for_liquidity_bps: u16,
     ) -> Result<()> {
         initialize_kickstarter::handler(
             ctx,
@@ -45,6 +66,22 @@ pub mod kickstarter {
             token_symbol,
             token_description,
             token_image_url,
+            private_round_attester,
+            hard_cap,
+            close_on_hard_cap,
+            withdrawal_cutoff_seconds,
+            vesting_tge_bps,
+            vesting_cliff_seconds,
+            vesting_duration_seconds,
+            price_feed,
+            price_feed_id,
+            max_price_age_seconds,
+            max_price_confidence_bps,
+            twap_window_seconds,
+            performance_package_expiry_seconds,
+            liquidity_lock_seconds,
+            liquidity_bps,
+            base_tokens_for_liquidity_bps,
         )
     }
 
@@ -56,6 +93,18 @@ pub mod kickstarter {
         fund::handler(ctx, amount)
     }
 
+    pub fn withdraw_commitment(ctx: Context<WithdrawCommitment>, amount: u64) -> Result<()> {
+        withdraw_commitment::handler(ctx, amount)
+    }
+
+    pub fn deposit_private(ctx: Context<DepositPrivate>, amount: u64) -> Result<()> {
+        deposit_private::handler(ctx, amount)
+    }
+
+    pub fn withdraw_private_deposit(ctx: Context<WithdrawPrivateDeposit>) -> Result<()> {
+        withdraw_private_deposit::handler(ctx)
+    }
+
     pub fn fund_private(ctx: Context<FundPrivate>, amount: u64, salt: [u8; 32]) -> Result<()> {
         fund_private::handler(ctx, amount, salt)
     }
@@ -72,25 +121,31 @@ pub mod kickstarter {
     pub fn private_claim(
         ctx: Context<PrivateClaim>,
         amount: u64,
-        salt: [u8; 32]
+        salt: [u8; 32],
+        leaf_index: u32,
+        proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        private_claim::handler(ctx, amount, salt)
+        private_claim::handler(ctx, amount, salt, leaf_index, proof)
     }
 
     pub fn private_claim_compressed(
         ctx: Context<PrivateClaimCompressed>,
         amount: u64,
-        salt: [u8; 32]
+        salt: [u8; 32],
+        leaf_index: u32,
+        proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        private_claim_compressed::handler(ctx, amount, salt)
+        private_claim_compressed::handler(ctx, amount, salt, leaf_index, proof)
     }
 
     pub fn private_refund(
         ctx: Context<PrivateRefund>,
         amount: u64,
-        salt: [u8; 32]
+        salt: [u8; 32],
+        leaf_index: u32,
+        proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        private_refund::handler(ctx, amount, salt)
+        private_refund::handler(ctx, amount, salt, leaf_index, proof)
     }
 
     pub fn start_private_round(ctx: Context<StartPrivateRound>) -> Result<()> {
@@ -112,6 +167,31 @@ pub mod kickstarter {
         complete_kickstarter::handler(ctx, final_raise_amount)
     }
 
+    pub fn trigger_refunds(ctx: Context<TriggerRefunds>) -> Result<()> {
+        trigger_refunds::handler(ctx)
+    }
+
+    pub fn seed_pool(ctx: Context<SeedPool>) -> Result<()> {
+        seed_pool::handler(ctx)
+    }
+
+    pub fn swap(
+        ctx: Context<Swap>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        base_to_quote: bool,
+    ) -> Result<()> {
+        swap::handler(ctx, amount_in, minimum_amount_out, base_to_quote)
+    }
+
+    pub fn withdraw_locked_liquidity(ctx: Context<WithdrawLockedLiquidity>) -> Result<()> {
+        withdraw_locked_liquidity::handler(ctx)
+    }
+
+    pub fn withdraw_team_budget(ctx: Context<WithdrawTeamBudget>, amount: u64) -> Result<()> {
+        withdraw_team_budget::handler(ctx, amount)
+    }
+
     pub fn claim(ctx: Context<Claim>) -> Result<()> {
         claim::handler(ctx)
     }
@@ -123,18 +203,32 @@ pub mod kickstarter {
     pub fn configure_performance_package(
         ctx: Context<ConfigurePerformancePackage>,
         index: u8,
-        multiplier: u8,
+        multiplier_bps: u32,
         allocation: u64,
+        recipient: Pubkey,
+        vesting_duration_seconds: u32,
+        depends_on_previous: bool,
     ) -> Result<()> {
-        configure_performance_package::handler(ctx, index, multiplier, allocation)
+        configure_performance_package::handler(
+            ctx,
+            index,
+            multiplier_bps,
+            allocation,
+            recipient,
+            vesting_duration_seconds,
+            depends_on_previous,
+        )
     }
 
     pub fn unlock_performance_package(
         ctx: Context<UnlockPerformancePackage>,
         index: u8,
-        current_price: u64,
     ) -> Result<()> {
-        unlock_performance_package::handler(ctx, index, current_price)
+        unlock_performance_package::handler(ctx, index)
+    }
+
+    pub fn record_price(ctx: Context<RecordPrice>) -> Result<()> {
+        record_price::handler(ctx)
     }
 
     pub fn claim_performance_package(
@@ -144,6 +238,21 @@ pub mod kickstarter {
         claim_performance_package::handler(ctx, index)
     }
 
+    pub fn release_unconfigured_performance_tokens(
+        ctx: Context<ReleaseUnconfiguredPerformanceTokens>,
+        release: PerformanceTokenRelease,
+    ) -> Result<()> {
+        release_unconfigured_performance_tokens::handler(ctx, release)
+    }
+
+    pub fn expire_performance_package(
+        ctx: Context<ExpirePerformancePackage>,
+        index: u8,
+        release: PerformanceTokenRelease,
+    ) -> Result<()> {
+        expire_performance_package::handler(ctx, index, release)
+    }
+
     pub fn set_minimum_raise(
         ctx: Context<SetMinimumRaise>,
         new_minimum_raise: u64,
@@ -151,6 +260,14 @@ pub mod kickstarter {
         set_minimum_raise::handler(ctx, new_minimum_raise)
     }
 
+    pub fn set_hard_cap(
+        ctx: Context<SetHardCap>,
+        new_hard_cap: u64,
+        close_on_hard_cap: bool,
+    ) -> Result<()> {
+        set_hard_cap::handler(ctx, new_hard_cap, close_on_hard_cap)
+    }
+
     pub fn stake_from_treasury(
         ctx: Context<StakeFromTreasury>,
         amount: u64,
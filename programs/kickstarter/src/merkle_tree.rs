@@ -1,132 +1,116 @@
 use anchor_lang::prelude::*;
 use sha2::{Digest, Sha256};
 
-pub const MAX_COMMITMENTS: usize = 256;
-pub const MAX_LEAVES_SIZE: usize = MAX_COMMITMENTS * 32;
-
-#[derive(Clone, Debug, AnchorSerialize, AnchorDeserialize, InitSpace)]
-pub struct MerkleTree {
-    #[max_len(MAX_COMMITMENTS)]
-    pub leaves: Vec<[u8; 32]>,
-    pub root: [u8; 32],
-    pub count: u32,
-}
+use crate::error::ErrorCode;
 
-impl Default for MerkleTree {
-    fn default() -> Self {
-        Self {
-            leaves: Vec::new(),
-            root: [0u8; 32],
-            count: 0,
-        }
-    }
-}
+/// Depth of the private-round commitment tree, i.e. up to 2^16 commitments.
+pub const MERKLE_TREE_DEPTH: usize = 16;
+pub const MAX_COMMITMENTS: u64 = 1 << MERKLE_TREE_DEPTH;
 
-impl MerkleTree {
-    pub fn new() -> Self {
-        Self::default()
-    }
+pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
 
-    pub fn add_commitment(&mut self, commitment: [u8; 32]) -> Result<()> {
-        require!(self.leaves.len() < MAX_COMMITMENTS, ErrorCode::InvalidCommitmentsRoot);
+/// Leaf of the tree: sha256(owner || amount_le || salt).
+pub fn commitment_hash(owner: &Pubkey, amount: u64, salt: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(owner.as_ref());
+    hasher.update(amount.to_le_bytes());
+    hasher.update(salt);
+    hasher.finalize().into()
+}
 
-        self.leaves.push(commitment);
-        self.count += 1;
-        self.update_root();
+/// Seed of the nullifier PDA that marks `commitment` as spent. Hashing again
+/// keeps the nullifier from being linked to its leaf in the tree.
+pub fn nullifier_hash(commitment: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"nullifier");
+    hasher.update(commitment);
+    hasher.finalize().into()
+}
 
-        Ok(())
+/// Roots of empty subtrees, `zeros[i]` is the root of an empty subtree of height `i`.
+pub fn zero_hashes() -> [[u8; 32]; MERKLE_TREE_DEPTH + 1] {
+    let mut zeros = [[0u8; 32]; MERKLE_TREE_DEPTH + 1];
+    for level in 0..MERKLE_TREE_DEPTH {
+        zeros[level + 1] = hash_pair(&zeros[level], &zeros[level]);
     }
+    zeros
+}
 
-    pub fn update_root(&mut self) {
-        if self.leaves.is_empty() {
-            self.root = [0u8; 32];
-            return;
-        }
-
-        let mut current_level = self.leaves.clone();
-
-        while current_level.len() > 1 {
-            let mut next_level = Vec::new();
-
-            for i in (0..current_level.len()).step_by(2) {
-                let left = current_level[i];
-                let right = if i + 1 < current_level.len() {
-                    current_level[i + 1]
-                } else {
-                    left
-                };
-
-                let mut hasher = Sha256::new();
-                hasher.update(left);
-                hasher.update(right);
-                let hash = hasher.finalize();
-                next_level.push(hash.into());
-            }
+pub fn empty_root() -> [u8; 32] {
+    zero_hashes()[MERKLE_TREE_DEPTH]
+}
 
-            current_level = next_level;
+/// Appends `leaf` at position `next_index`, updating the frontier in place and
+/// returning the new root.
+pub fn append_leaf(
+    filled_subtrees: &mut [[u8; 32]; MERKLE_TREE_DEPTH],
+    next_index: u32,
+    leaf: [u8; 32],
+) -> Result<[u8; 32]> {
+    require!((next_index as u64) < MAX_COMMITMENTS, ErrorCode::MerkleTreeFull);
+
+    let zeros = zero_hashes();
+    let mut current_index = next_index;
+    let mut current_hash = leaf;
+
+    for level in 0..MERKLE_TREE_DEPTH {
+        if current_index & 1 == 0 {
+            filled_subtrees[level] = current_hash;
+            current_hash = hash_pair(&current_hash, &zeros[level]);
+        } else {
+            current_hash = hash_pair(&filled_subtrees[level], &current_hash);
         }
-
-        self.root = current_level[0];
+        current_index /= 2;
     }
 
-    pub fn verify_commitment(&self, commitment: [u8; 32], index: usize) -> bool {
-        if index >= self.leaves.len() || self.leaves[index] != commitment {
-            return false;
-        }
+    Ok(current_hash)
+}
 
-        let mut current_hash = commitment;
-        let mut current_index = index;
-
-        let mut level = self.leaves.clone();
-
-        while level.len() > 1 {
-            let sibling_index = if current_index % 2 == 0 {
-                current_index + 1
-            } else {
-                current_index - 1
-            };
-
-            let sibling_hash = if sibling_index < level.len() {
-                level[sibling_index]
-            } else {
-                current_hash
-            };
-
-            let mut hasher = Sha256::new();
-            if current_index % 2 == 0 {
-                hasher.update(current_hash);
-                hasher.update(sibling_hash);
-            } else {
-                hasher.update(sibling_hash);
-                hasher.update(current_hash);
-            }
-
-            current_hash = hasher.finalize().into();
-            current_index /= 2;
-
-            let mut next_level = Vec::new();
-            for i in (0..level.len()).step_by(2) {
-                let left = level[i];
-                let right = if i + 1 < level.len() {
-                    level[i + 1]
-                } else {
-                    left
-                };
-
-                let mut hasher = Sha256::new();
-                hasher.update(left);
-                hasher.update(right);
-                next_level.push(hasher.finalize().into());
-            }
-            level = next_level;
-        }
+/// Checks that `leaf` sits at `leaf_index` under `root`, `proof` being the
+/// sibling hashes from the leaf level upwards.
+pub fn verify_proof(root: &[u8; 32], leaf: [u8; 32], leaf_index: u32, proof: &[[u8; 32]]) -> bool {
+    if proof.len() != MERKLE_TREE_DEPTH || (leaf_index as u64) >= MAX_COMMITMENTS {
+        return false;
+    }
 
-        current_hash == self.root
+    let mut current_index = leaf_index;
+    let mut current_hash = leaf;
+    for sibling in proof {
+        current_hash = if current_index & 1 == 0 {
+            hash_pair(&current_hash, sibling)
+        } else {
+            hash_pair(sibling, &current_hash)
+        };
+        current_index /= 2;
     }
 
-    pub fn get_root(&self) -> [u8; 32] {
-        self.root
+    current_hash == *root
+}
+
+/// Inclusion proof for `leaves[index]`, as clients build it.
+pub fn compute_proof(leaves: &[[u8; 32]], index: usize) -> Vec<[u8; 32]> {
+    let zeros = zero_hashes();
+    let mut proof = Vec::with_capacity(MERKLE_TREE_DEPTH);
+    let mut level_nodes = leaves.to_vec();
+    let mut current_index = index;
+
+    for zero in zeros.iter().take(MERKLE_TREE_DEPTH) {
+        let sibling_index = current_index ^ 1;
+        proof.push(level_nodes.get(sibling_index).copied().unwrap_or(*zero));
+
+        level_nodes = level_nodes
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(zero)))
+            .collect();
+        current_index /= 2;
     }
+
+    proof
 }
 
 #[cfg(test)]
@@ -135,16 +119,32 @@ mod tests {
 
     #[test]
     fn test_merkle_tree_basic() {
-        let mut tree = MerkleTree::new();
+        let mut filled_subtrees = [[0u8; 32]; MERKLE_TREE_DEPTH];
 
         let commitment1 = [1u8; 32];
         let commitment2 = [2u8; 32];
 
-        tree.add_commitment(commitment1).unwrap();
-        tree.add_commitment(commitment2).unwrap();
+        append_leaf(&mut filled_subtrees, 0, commitment1).unwrap();
+        let root = append_leaf(&mut filled_subtrees, 1, commitment2).unwrap();
 
-        assert_eq!(tree.count, 2);
-        assert!(tree.verify_commitment(commitment1, 0));
-        assert!(tree.verify_commitment(commitment2, 1));
+        let leaves = [commitment1, commitment2];
+        assert!(verify_proof(&root, commitment1, 0, &compute_proof(&leaves, 0)));
+        assert!(verify_proof(&root, commitment2, 1, &compute_proof(&leaves, 1)));
+        assert!(!verify_proof(&root, commitment2, 0, &compute_proof(&leaves, 0)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_every_leaf_stays_provable() {
+        let mut filled_subtrees = [[0u8; 32]; MERKLE_TREE_DEPTH];
+        let leaves: Vec<[u8; 32]> = (0..7u8).map(|i| [i + 1; 32]).collect();
+
+        let mut root = empty_root();
+        for (i, leaf) in leaves.iter().enumerate() {
+            root = append_leaf(&mut filled_subtrees, i as u32, *leaf).unwrap();
+        }
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            assert!(verify_proof(&root, *leaf, i as u32, &compute_proof(&leaves, i)));
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::BPS_DENOMINATOR;
+use crate::error::ErrorCode;
+
+/// Owner of Pyth pull-oracle `PriceUpdateV2` accounts.
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Decimals new raises record in `Kickstarter::token_price_decimals`.
+pub const TOKEN_PRICE_DECIMALS: u8 = 12;
+
+const WRITE_AUTHORITY_SIZE: usize = 32;
+const FEED_ID_SIZE: usize = 32;
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+/// Parses a `PriceUpdateV2` account:
+/// discriminator || write_authority || verification_level || feed_id || price || conf
+/// || exponent || publish_time || ... (all little endian). Only fully verified
+/// updates for `expected_feed_id` are accepted.
+pub fn load_price(price_feed: &AccountInfo, expected_feed_id: &[u8; 32]) -> Result<OraclePrice> {
+    require_keys_eq!(*price_feed.owner, PYTH_RECEIVER_PROGRAM_ID, ErrorCode::InvalidPriceFeed);
+
+    let data = price_feed.try_borrow_data()?;
+    require!(
+        data.len() > 8 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+        ErrorCode::InvalidPriceFeed
+    );
+
+    let mut offset = 8 + WRITE_AUTHORITY_SIZE;
+    let verification_level = *data.get(offset).ok_or(ErrorCode::InvalidPriceFeed)?;
+    require!(verification_level == VERIFICATION_LEVEL_FULL, ErrorCode::InvalidPriceFeed);
+    offset += 1;
+
+    let feed_id: [u8; FEED_ID_SIZE] = read_bytes(&data, &mut offset)?;
+    require!(feed_id == *expected_feed_id, ErrorCode::InvalidPriceFeed);
+
+    let price = i64::from_le_bytes(read_bytes(&data, &mut offset)?);
+    let conf = u64::from_le_bytes(read_bytes(&data, &mut offset)?);
+    let exponent = i32::from_le_bytes(read_bytes(&data, &mut offset)?);
+    let publish_time = i64::from_le_bytes(read_bytes(&data, &mut offset)?);
+
+    Ok(OraclePrice {
+        price,
+        conf,
+        exponent,
+        publish_time,
+    })
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: &mut usize) -> Result<[u8; N]> {
+    let bytes = data
+        .get(*offset..*offset + N)
+        .ok_or(ErrorCode::InvalidPriceFeed)?;
+    *offset += N;
+    Ok(bytes.try_into().unwrap())
+}
+
+impl OraclePrice {
+    /// Rejects prices older than `max_age_seconds` or with a confidence interval
+    /// wider than `max_confidence_bps` of the price.
+    pub fn validate(&self, now: i64, max_age_seconds: u32, max_confidence_bps: u16) -> Result<()> {
+        require!(self.price > 0, ErrorCode::InvalidPriceFeed);
+
+        let age = now.saturating_sub(self.publish_time);
+        require!(age <= max_age_seconds as i64, ErrorCode::StalePrice);
+
+        let conf_scaled = (self.conf as u128)
+            .checked_mul(BPS_DENOMINATOR as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let max_conf_scaled = (self.price as u128)
+            .checked_mul(max_confidence_bps as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(conf_scaled <= max_conf_scaled, ErrorCode::PriceConfidenceTooWide);
+
+        Ok(())
+    }
+
+    /// Converts the whole-quote-per-whole-base price to `price_decimals` fixed point.
+    pub fn to_token_price(&self, price_decimals: u8) -> Result<u64> {
+        rescale(self.price as u128, 1, self.exponent + price_decimals as i32)
+    }
+}
+
+/// Price of `base_amount` raw base tokens paid with `quote_amount` raw quote tokens,
+/// in whole quote per whole base with `price_decimals` decimals.
+pub fn token_price(
+    quote_amount: u64,
+    base_amount: u64,
+    base_decimals: u8,
+    quote_decimals: u8,
+    price_decimals: u8,
+) -> Result<u64> {
+    let shift = price_decimals as i32 + base_decimals as i32 - quote_decimals as i32;
+    rescale(quote_amount as u128, base_amount as u128, shift)
+}
+
+/// `numerator * 10^shift / denominator`
+fn rescale(numerator: u128, denominator: u128, shift: i32) -> Result<u64> {
+    require!(denominator > 0, ErrorCode::MathOverflow);
+    let scale = 10u128
+        .checked_pow(shift.unsigned_abs())
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let scaled = if shift >= 0 {
+        numerator.checked_mul(scale).ok_or(ErrorCode::MathOverflow)? / denominator
+    } else {
+        numerator / denominator.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?
+    };
+    Ok(u64::try_from(scaled).map_err(|_| ErrorCode::MathOverflow)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_price_normalizes_decimals() {
+        // 1_000 USDC (6 decimals) for 1_000 tokens with 9 decimals is 1.0
+        assert_eq!(
+            token_price(1_000_000_000, 1_000_000_000_000, 9, 6, TOKEN_PRICE_DECIMALS).unwrap(),
+            1_000_000_000_000
+        );
+        // same raise with 6 decimal base tokens
+        assert_eq!(
+            token_price(1_000_000_000, 1_000_000_000, 6, 6, TOKEN_PRICE_DECIMALS).unwrap(),
+            1_000_000_000_000
+        );
+        // 0.25 with a 2 decimal scale
+        assert_eq!(token_price(250_000, 1_000_000_000, 9, 6, 2).unwrap(), 25);
+        assert!(token_price(1, 0, 6, 6, TOKEN_PRICE_DECIMALS).is_err());
+    }
+
+    #[test]
+    fn test_oracle_price_matches_token_price_scale() {
+        let oracle_price = OraclePrice {
+            price: 123_450,
+            conf: 0,
+            exponent: -5,
+            publish_time: 0,
+        };
+        assert_eq!(oracle_price.to_token_price(TOKEN_PRICE_DECIMALS).unwrap(), 1_234_500_000_000);
+        assert_eq!(oracle_price.to_token_price(2).unwrap(), 123);
+    }
+}
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 use std::fmt::Debug;
 
-use crate::constants::MAX_PERFORMANCE_PACKAGES;
+use crate::constants::{BPS_DENOMINATOR, TEAM_BUDGET_PERIOD_SECONDS};
+use crate::error::ErrorCode;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum KickstarterState {
@@ -12,16 +13,6 @@ pub enum KickstarterState {
     Refunding,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Default)]
-pub struct PerformancePackage {
-    pub multiplier: u8,
-    pub allocation: u64,
-    pub is_configured: bool,
-    pub is_unlocked: bool,
-    pub is_claimed: bool,
-    pub unlocked_at: Option<i64>,
-}
-
 #[account]
 #[derive(InitSpace)]
 pub struct Kickstarter {
@@ -32,6 +23,7 @@ pub struct Kickstarter {
     pub quote_mint: Pubkey,
     pub base_vault: Pubkey,
     pub quote_vault: Pubkey,
+    pub private_quote_vault: Pubkey,
     pub treasury: Pubkey,
     pub minimum_raise_amount: u64,
     pub total_base_tokens_for_investors: u64,
@@ -39,16 +31,19 @@ pub struct Kickstarter {
     /// независимое от min_raise_amount, служит "потолком-лимитом"" для пополнеия от fund'еров
     pub hard_cap: u64, //hard cap in quote tokens
     pub final_raise_amount: Option<u64>,
+    /// public + private commitments at completion, the base for every pro-rata split
     pub total_committed_at_completion: Option<u64>,
     pub seconds_for_launch: u32,
     pub unix_timestamp_started: Option<i64>,
     pub unix_timestamp_closed: Option<i64>,
     pub performance_pool_base_tokens: u64,
     pub configured_performance_tokens: u64,
-    pub performance_packages: [PerformancePackage; MAX_PERFORMANCE_PACKAGES],
+    /// packages are PDAs at indexes 0..performance_package_count
+    pub performance_package_count: u8,
     pub monthly_team_spending_usdc: u64,
     pub package_unlock_delay_seconds: i64,
     pub calculated_liquidity_amount: Option<u64>,
+    /// whole quote per whole base, token_price_decimals fixed point
     pub initial_token_price: Option<u64>,
     pub calculated_base_tokens_for_investors: Option<u64>,
     pub calculated_base_tokens_for_liquidity: Option<u64>,
@@ -56,6 +51,148 @@ pub struct Kickstarter {
     pub private_commitments_root: [u8; 32],
     pub private_investor_count: u32,
     pub is_private_round_active: bool,
+    /// signs (kickstarter, commitments root, total) when the private round is finalized
+    pub private_round_attester: Pubkey,
+    /// reaching hard_cap moves the deadline to now, so the raise can be completed right away
+    pub close_on_hard_cap: bool,
+    /// withdraw_commitment is blocked during this many seconds before the deadline
+    pub withdrawal_cutoff_seconds: u32,
+    /// vesting of investor claims, counted from completion: vesting_tge_bps right away,
+    /// the rest linearly over vesting_duration_seconds with nothing of it before the cliff
+    pub vesting_tge_bps: u16,
+    pub vesting_cliff_seconds: u32,
+    pub vesting_duration_seconds: u32,
+    /// Pyth PriceUpdateV2 account read by unlock_performance_package
+    pub price_feed: Pubkey,
+    /// Pyth feed id the price_feed account has to carry
+    pub price_feed_id: [u8; 32],
+    pub max_price_age_seconds: u32,
+    /// max confidence interval, in bps of the price
+    pub max_price_confidence_bps: u16,
+    /// performance packages unlock on the average price over this many seconds
+    pub twap_window_seconds: u32,
+    /// packages still locked this many seconds after completion can be expired, 0 = never
+    pub performance_package_expiry_seconds: u32,
+    /// the part of the performance pool no package was configured for has been released
+    pub unconfigured_performance_released: bool,
+    /// LP tokens minted at completion stay in the lp vault this long
+    pub liquidity_lock_seconds: u32,
+    /// seed_pool time + liquidity_lock_seconds, withdraw_locked_liquidity works from then on
+    pub liquidity_unlock_timestamp: Option<i64>,
+    /// share of the final raise that seeds the pool
+    pub liquidity_bps: u16,
+    /// base tokens for the pool, in bps of total_base_tokens_for_investors,
+    /// 0 = derive them from the quote side at the sale price
+    pub base_tokens_for_liquidity_bps: u16,
+    /// decimals of initial_token_price and every price compared with it,
+    /// which are whole quote per whole base
+    pub token_price_decimals: u8,
+    /// program-owned vault holding the quote left after liquidity
+    pub treasury_vault: Pubkey,
+    /// team budget accrual cursor, whole TEAM_BUDGET_PERIOD_SECONDS up to here are accrued
+    pub team_budget_accrued_until: Option<i64>,
+    /// accrued and not yet withdrawn team budget
+    pub team_budget_available: u64,
+}
+
+impl Kickstarter {
+    /// Whether locked performance packages are past their expiry at `now`.
+    pub fn performance_packages_expired(&self, now: i64) -> Result<bool> {
+        if self.performance_package_expiry_seconds == 0 {
+            return Ok(false);
+        }
+        let completed_at = self
+            .unix_timestamp_closed
+            .ok_or(ErrorCode::InvalidKickstarterState)?;
+        let expires_at = completed_at
+            .checked_add(self.performance_package_expiry_seconds as i64)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(now >= expires_at)
+    }
+
+    /// Whether seed_pool has run; claims and the team budget wait for it.
+    pub fn is_pool_seeded(&self) -> bool {
+        self.treasury_vault != Pubkey::default()
+    }
+
+    /// Public and private commitments together, what `hard_cap` limits.
+    pub fn combined_committed_amount(&self, private_committed_amount: u64) -> Result<u64> {
+        Ok(self
+            .total_committed_amount
+            .checked_add(private_committed_amount)
+            .ok_or(ErrorCode::MathOverflow)?)
+    }
+
+    /// Moves the accrual cursor over the whole periods elapsed by `now`, adding
+    /// monthly_team_spending_usdc to the available budget for each.
+    pub fn accrue_team_budget(&mut self, now: i64) -> Result<()> {
+        let accrued_until = self
+            .team_budget_accrued_until
+            .ok_or(ErrorCode::InvalidKickstarterState)?;
+        let periods = now.saturating_sub(accrued_until) / TEAM_BUDGET_PERIOD_SECONDS;
+        if periods <= 0 {
+            return Ok(());
+        }
+
+        let accrued = (periods as u64)
+            .checked_mul(self.monthly_team_spending_usdc)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.team_budget_available = self
+            .team_budget_available
+            .checked_add(accrued)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.team_budget_accrued_until = Some(
+            accrued_until
+                .checked_add(periods * TEAM_BUDGET_PERIOD_SECONDS)
+                .ok_or(ErrorCode::MathOverflow)?,
+        );
+        Ok(())
+    }
+
+    /// Base tokens paired with `liquidity_amount` of quote in the pool.
+    pub fn base_tokens_for_liquidity(&self, liquidity_amount: u64, final_raise_amount: u64) -> Result<u64> {
+        let base_tokens_u128 = if self.base_tokens_for_liquidity_bps == 0 {
+            // same ratio as the sale, so the pool opens at initial_token_price
+            (liquidity_amount as u128)
+                .checked_mul(self.total_base_tokens_for_investors as u128)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_div(final_raise_amount as u128)
+                .ok_or(ErrorCode::MathOverflow)?
+        } else {
+            (self.total_base_tokens_for_investors as u128)
+                .checked_mul(self.base_tokens_for_liquidity_bps as u128)
+                .ok_or(ErrorCode::MathOverflow)?
+                / BPS_DENOMINATOR as u128
+        };
+        Ok(u64::try_from(base_tokens_u128).map_err(|_| ErrorCode::MathOverflow)?)
+    }
+
+    /// Part of `entitlement` unlocked at `now` for a raise completed at `vesting_start`.
+    pub fn vested_base_tokens(&self, entitlement: u64, vesting_start: i64, now: i64) -> Result<u64> {
+        let tge_amount = (entitlement as u128)
+            .checked_mul(self.vesting_tge_bps as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(BPS_DENOMINATOR as u128)
+            .ok_or(ErrorCode::MathOverflow)? as u64;
+        let linear_amount = entitlement
+            .checked_sub(tge_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let elapsed = now.saturating_sub(vesting_start).max(0) as u64;
+        if elapsed < self.vesting_cliff_seconds as u64 {
+            return Ok(tge_amount);
+        }
+        if elapsed >= self.vesting_duration_seconds as u64 {
+            return Ok(entitlement);
+        }
+
+        let vested_linear = (linear_amount as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(self.vesting_duration_seconds as u128)
+            .ok_or(ErrorCode::MathOverflow)? as u64;
+        Ok(tge_amount.checked_add(vested_linear).ok_or(ErrorCode::MathOverflow)?)
+    }
 }
 
 impl Debug for KickstarterState {
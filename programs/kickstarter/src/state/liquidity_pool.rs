@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Constant-product pool created and seeded by `seed_pool`. The
+/// kickstarter PDA owns both reserve vaults, the LP mint and the LP vault.
+#[account]
+#[derive(InitSpace)]
+pub struct LiquidityPool {
+    pub kickstarter: Pubkey,
+    pub base_vault: Pubkey,
+    pub quote_vault: Pubkey,
+    pub lp_mint: Pubkey,
+    /// LP tokens minted for the initial liquidity
+    pub lp_vault: Pubkey,
+    pub fee_bps: u16,
+    pub bump: u8,
+}
@@ -1,10 +1,20 @@
 pub mod kickstarter;
 pub mod private_state;
 pub mod funder_position;
+pub mod nullifier;
+pub mod private_deposit;
+pub mod price_observations;
+pub mod performance_package;
+pub mod liquidity_pool;
 
 pub use funder_position::*;
 pub use kickstarter::*;
+pub use nullifier::*;
+pub use private_deposit::*;
 pub use private_state::*;
+pub use price_observations::*;
+pub use performance_package::*;
+pub use liquidity_pool::*;
 
 
 
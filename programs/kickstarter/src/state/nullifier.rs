@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// Created on the first claim or refund of a private commitment, so the same
+/// `(amount, salt)` cannot be spent twice. Claims follow the raise's vesting
+/// schedule, so the claimed base tokens are tracked rather than a flag.
+#[account]
+#[derive(InitSpace)]
+pub struct CommitmentNullifier {
+    pub kickstarter: Pubkey,
+    pub nullifier: [u8; 32],
+    pub claimed_amount: u64,
+    pub refunded: bool,
+    pub bump: u8,
+}
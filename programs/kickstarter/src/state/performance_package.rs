@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::BPS_DENOMINATOR;
+use crate::error::ErrorCode;
+
+/// One milestone of the performance pool, a PDA per (kickstarter, index).
+#[account]
+#[derive(InitSpace)]
+pub struct PerformancePackage {
+    pub kickstarter: Pubkey,
+    pub index: u8,
+    /// price target over initial_token_price, 15_000 = 1.5x
+    pub multiplier_bps: u32,
+    pub allocation: u64,
+    /// unlocks only after the package at index - 1, and counts its delay from that unlock
+    pub depends_on_previous: bool,
+    pub is_unlocked: bool,
+    pub unlocked_at: Option<i64>,
+    /// only this owner's base token account can receive the package
+    pub recipient: Pubkey,
+    /// allocation vests linearly over this many seconds after unlocked_at, 0 = all at unlock
+    pub vesting_duration_seconds: u32,
+    pub claimed_amount: u64,
+    /// expired before unlocking, the allocation was burned or sent to the treasury
+    pub is_expired: bool,
+    pub bump: u8,
+}
+
+/// Where released performance pool tokens go.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PerformanceTokenRelease {
+    Burn,
+    Treasury,
+}
+
+impl PerformancePackage {
+    /// `initial_price * multiplier_bps / BPS_DENOMINATOR`, rounded up so a
+    /// fractional target is never undercut.
+    pub fn target_price(&self, initial_price: u64) -> Result<u64> {
+        let scaled = (initial_price as u128)
+            .checked_mul(self.multiplier_bps as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let target = scaled.div_ceil(BPS_DENOMINATOR as u128);
+        Ok(u64::try_from(target).map_err(|_| ErrorCode::MathOverflow)?)
+    }
+
+    /// Part of the allocation vested at `now`, nothing while still locked.
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        let Some(unlocked_at) = self.unlocked_at else {
+            return Ok(0);
+        };
+        let elapsed = now.saturating_sub(unlocked_at).max(0) as u64;
+        if elapsed >= self.vesting_duration_seconds as u64 {
+            return Ok(self.allocation);
+        }
+
+        let vested = (self.allocation as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(self.vesting_duration_seconds as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(u64::try_from(vested).map_err(|_| ErrorCode::MathOverflow)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(multiplier_bps: u32) -> PerformancePackage {
+        PerformancePackage {
+            kickstarter: Pubkey::default(),
+            index: 0,
+            multiplier_bps,
+            allocation: 0,
+            depends_on_previous: false,
+            is_unlocked: false,
+            unlocked_at: None,
+            recipient: Pubkey::default(),
+            vesting_duration_seconds: 0,
+            claimed_amount: 0,
+            is_expired: false,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_target_price_fractional_multipliers() {
+        assert_eq!(package(20_000).target_price(100_000).unwrap(), 200_000);
+        assert_eq!(package(15_000).target_price(100_000).unwrap(), 150_000);
+        assert_eq!(package(22_500).target_price(100_000).unwrap(), 225_000);
+        // 333 * 1.5 = 499.5 rounds up
+        assert_eq!(package(15_000).target_price(333).unwrap(), 500);
+        assert!(package(u32::MAX).target_price(u64::MAX).is_err());
+    }
+}
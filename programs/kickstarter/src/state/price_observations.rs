@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_PRICE_OBSERVATIONS, MIN_TWAP_OBSERVATIONS};
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Default)]
+pub struct PriceObservation {
+    pub timestamp: i64,
+    /// sum of price * seconds since the first observation
+    pub cumulative_price: u128,
+}
+
+/// Ring buffer of price accumulator snapshots filled by `record_price`. Each
+/// recorded price is held until the next one, but for no longer than `max_hold`,
+/// so a one-off spike only weighs in for a single crank however long the gap
+/// after it; uncovered time counts as a zero price.
+#[account]
+#[derive(InitSpace)]
+pub struct PriceObservations {
+    pub kickstarter: Pubkey,
+    pub last_price: u64,
+    pub count: u16,
+    /// slot of the newest observation
+    pub head: u16,
+    pub observations: [PriceObservation; MAX_PRICE_OBSERVATIONS],
+    pub bump: u8,
+}
+
+impl PriceObservations {
+    pub fn newest(&self) -> Option<&PriceObservation> {
+        (self.count > 0).then(|| &self.observations[self.head as usize])
+    }
+
+    /// Spacing between observations so that the buffer spans two windows.
+    pub fn min_interval(twap_window_seconds: u32) -> i64 {
+        (twap_window_seconds as i64 / (MAX_PRICE_OBSERVATIONS as i64 / 2)).max(1)
+    }
+
+    /// Longest time one observation is credited for, and the oldest the newest
+    /// observation may be when the TWAP is read.
+    pub fn max_hold(twap_window_seconds: u32) -> i64 {
+        2 * Self::min_interval(twap_window_seconds)
+    }
+
+    pub fn record(&mut self, now: i64, price: u64, twap_window_seconds: u32) -> Result<()> {
+        let observation = match self.newest() {
+            None => PriceObservation {
+                timestamp: now,
+                cumulative_price: 0,
+            },
+            Some(newest) => {
+                let elapsed = now.saturating_sub(newest.timestamp);
+                require!(
+                    elapsed >= Self::min_interval(twap_window_seconds),
+                    ErrorCode::PriceObservationTooSoon
+                );
+                let held = elapsed.min(Self::max_hold(twap_window_seconds));
+                let cumulative_price = (self.last_price as u128)
+                    .checked_mul(held as u128)
+                    .and_then(|held| newest.cumulative_price.checked_add(held))
+                    .ok_or(ErrorCode::MathOverflow)?;
+                PriceObservation {
+                    timestamp: now,
+                    cumulative_price,
+                }
+            }
+        };
+
+        if self.count > 0 {
+            self.head = (self.head + 1) % MAX_PRICE_OBSERVATIONS as u16;
+        }
+        self.observations[self.head as usize] = observation;
+        self.count = self.count.saturating_add(1).min(MAX_PRICE_OBSERVATIONS as u16);
+        self.last_price = price;
+        Ok(())
+    }
+
+    /// Time-weighted average price over at least `twap_window_seconds` ending at
+    /// the newest observation, which must be fresh at `now`.
+    pub fn twap(&self, twap_window_seconds: u32, now: i64) -> Result<u64> {
+        let newest = self.newest().ok_or(ErrorCode::InsufficientPriceHistory)?;
+        require!(
+            now.saturating_sub(newest.timestamp) <= Self::max_hold(twap_window_seconds),
+            ErrorCode::StalePriceObservations
+        );
+        let window_start = newest.timestamp.saturating_sub(twap_window_seconds as i64);
+
+        // walk back from the newest to the latest observation at or before window_start
+        let (age, start) = (1..self.count)
+            .map(|age| {
+                let slot = (self.head as usize + MAX_PRICE_OBSERVATIONS - age as usize) % MAX_PRICE_OBSERVATIONS;
+                (age, &self.observations[slot])
+            })
+            .find(|(_, observation)| observation.timestamp <= window_start)
+            .ok_or(ErrorCode::InsufficientPriceHistory)?;
+        // observations after the start, the newest included
+        require!(age as usize >= MIN_TWAP_OBSERVATIONS, ErrorCode::InsufficientPriceHistory);
+
+        let elapsed = newest.timestamp.saturating_sub(start.timestamp);
+        require!(elapsed > 0, ErrorCode::InsufficientPriceHistory);
+        let twap = newest
+            .cumulative_price
+            .checked_sub(start.cumulative_price)
+            .ok_or(ErrorCode::MathOverflow)?
+            / elapsed as u128;
+        Ok(u64::try_from(twap).map_err(|_| ErrorCode::MathOverflow)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: u32 = 3_200;
+
+    fn empty() -> PriceObservations {
+        PriceObservations {
+            kickstarter: Pubkey::default(),
+            last_price: 0,
+            count: 0,
+            head: 0,
+            observations: [PriceObservation::default(); MAX_PRICE_OBSERVATIONS],
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_twap_dampens_single_spike() {
+        let mut observations = empty();
+        let interval = PriceObservations::min_interval(WINDOW);
+        for step in 0..=32 {
+            let price = if step == 31 { 1_000 } else { 100 };
+            observations.record(step * interval, price, WINDOW).unwrap();
+        }
+
+        // the spike is held for one interval out of 32
+        assert_eq!(observations.twap(WINDOW, 32 * interval).unwrap(), (31 * 100 + 1_000) / 32);
+    }
+
+    #[test]
+    fn test_twap_needs_full_window() {
+        let mut observations = empty();
+        let interval = PriceObservations::min_interval(WINDOW);
+        assert!(observations.twap(WINDOW, 0).is_err());
+
+        for step in 0..32 {
+            observations.record(step * interval, 100, WINDOW).unwrap();
+        }
+        assert!(observations.twap(WINDOW, 31 * interval).is_err());
+
+        observations.record(32 * interval, 100, WINDOW).unwrap();
+        assert_eq!(observations.twap(WINDOW, 32 * interval).unwrap(), 100);
+    }
+
+    #[test]
+    fn test_twap_needs_enough_observations_in_window() {
+        let mut observations = empty();
+        let interval = PriceObservations::min_interval(WINDOW);
+        // one sample before the window, one short of the minimum inside it
+        observations.record(0, 100, WINDOW).unwrap();
+        for k in (0..MIN_TWAP_OBSERVATIONS as i64 - 1).rev() {
+            observations.record(WINDOW as i64 - k * interval, 100, WINDOW).unwrap();
+        }
+        assert!(observations.twap(WINDOW, WINDOW as i64).is_err());
+
+        observations.record(WINDOW as i64 + interval, 100, WINDOW).unwrap();
+        assert!(observations.twap(WINDOW, WINDOW as i64 + interval).is_ok());
+    }
+
+    #[test]
+    fn test_gap_after_pump_does_not_carry_price() {
+        let mut observations = empty();
+        let interval = PriceObservations::min_interval(WINDOW);
+        for step in 0..=32 {
+            observations.record(step * interval, 100, WINDOW).unwrap();
+        }
+
+        // pumped for one crank, next sample half a window later
+        let pumped_at = 33 * interval;
+        let resumed_at = pumped_at + WINDOW as i64 / 2;
+        observations.record(pumped_at, 100_000, WINDOW).unwrap();
+        for step in 0..MIN_TWAP_OBSERVATIONS as i64 {
+            observations.record(resumed_at + step * interval, 100, WINDOW).unwrap();
+        }
+
+        // the pump is credited for max_hold, not for the whole gap
+        let now = observations.newest().unwrap().timestamp;
+        let max_hold = PriceObservations::max_hold(WINDOW) as u64;
+        let twap = observations.twap(WINDOW, now).unwrap();
+        assert!(twap <= 100 + 100_000 * max_hold / WINDOW as u64);
+    }
+
+    #[test]
+    fn test_twap_rejects_stale_newest_observation() {
+        let mut observations = empty();
+        let interval = PriceObservations::min_interval(WINDOW);
+        for step in 0..=32 {
+            observations.record(step * interval, 100, WINDOW).unwrap();
+        }
+
+        let newest = 32 * interval;
+        let max_hold = PriceObservations::max_hold(WINDOW);
+        assert!(observations.twap(WINDOW, newest + max_hold).is_ok());
+        assert!(observations.twap(WINDOW, newest + max_hold + 1).is_err());
+    }
+
+    #[test]
+    fn test_record_enforces_interval_and_wraps() {
+        let mut observations = empty();
+        let interval = PriceObservations::min_interval(WINDOW);
+        observations.record(0, 100, WINDOW).unwrap();
+        assert!(observations.record(interval - 1, 100, WINDOW).is_err());
+
+        for step in 1..=(2 * MAX_PRICE_OBSERVATIONS as i64) {
+            observations.record(step * interval, 200, WINDOW).unwrap();
+        }
+        assert_eq!(observations.count as usize, MAX_PRICE_OBSERVATIONS);
+        let now = 2 * MAX_PRICE_OBSERVATIONS as i64 * interval;
+        assert_eq!(observations.twap(WINDOW, now).unwrap(), 200);
+    }
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Quote tokens a funder escrowed for the private round. Delegated to the
+/// ephemeral rollup, where `fund_private` moves part of `balance` into a commitment.
+#[account]
+#[derive(InitSpace)]
+pub struct PrivateDeposit {
+    pub kickstarter: Pubkey,
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
@@ -1,11 +1,28 @@
 use anchor_lang::prelude::*;
 
+use crate::error::ErrorCode;
+use crate::merkle_tree::MERKLE_TREE_DEPTH;
+
 #[account]
 #[derive(InitSpace)]
 pub struct PrivateFundState {
     pub kickstarter: Pubkey,
     pub commitments_root: [u8; 32],
+    /// frontier of the incremental commitment tree, one node per level
+    pub filled_subtrees: [[u8; 32]; MERKLE_TREE_DEPTH],
     pub investor_count: u32,
     pub committed_amount: u64,
+    /// part of `committed_amount` already moved from the private vault into `quote_vault`
+    pub settled_amount: u64,
     pub bump: u8,
 }
+
+impl PrivateFundState {
+    /// `committed_amount` read on the base layer. Fails while the account is delegated
+    /// to the ephemeral rollup, the base-layer copy is stale then.
+    pub fn base_layer_committed_amount(private_state: &AccountInfo) -> Result<u64> {
+        require_keys_eq!(*private_state.owner, crate::ID, ErrorCode::PrivateStateDelegated);
+        let data = private_state.try_borrow_data()?;
+        Ok(Self::try_deserialize(&mut &data[..])?.committed_amount)
+    }
+}
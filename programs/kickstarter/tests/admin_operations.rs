@@ -1,9 +1,10 @@
 mod support;
 
 use anchor_lang::solana_program::program_pack::Pack;
-use kickstarter::state::KickstarterState;
-use solana_keypair::Keypair;
-use support::{InitConfig, KickstarterAccounts, Pubkey, Signer, TestHarness, TestResult};
+use kickstarter::{error::ErrorCode, state::{KickstarterState, PrivateFundState}};
+use support::{
+    assert_error_code, InitConfig, KickstarterAccounts, Pubkey, Signer, TestHarness, TestResult,
+};
 
 #[test]
 fn set_minimum_raise_succeeds() -> TestResult {
@@ -88,6 +89,7 @@ fn set_minimum_raise_from_complete_state_fails() -> TestResult {
         FINAL_RAISE_AMOUNT,
     )?;
 
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     harness.send(accounts.complete_ix(FINAL_RAISE_AMOUNT), &accounts.admin)?;
 
     let ix = accounts.set_minimum_raise_ix(2_000_000);
@@ -100,6 +102,103 @@ fn set_minimum_raise_from_complete_state_fails() -> TestResult {
     Ok(())
 }
 
+#[test]
+fn set_minimum_raise_keeps_hard_cap() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 1_000_000,
+        hard_cap: 5_000_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+
+    harness.send(accounts.set_minimum_raise_ix(2_000_000), &accounts.admin)?;
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.hard_cap, 5_000_000);
+
+    let result = harness.send(accounts.set_minimum_raise_ix(6_000_000), &accounts.admin);
+    assert_error_code(result, ErrorCode::InvalidMinimumRaiseAmount)?;
+
+    Ok(())
+}
+
+#[test]
+fn set_hard_cap_succeeds() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 1_000_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+
+    harness.send(accounts.set_hard_cap_ix(4_000_000, true), &accounts.admin)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.hard_cap, 4_000_000);
+    assert!(decoded.close_on_hard_cap);
+
+    Ok(())
+}
+
+#[test]
+fn set_hard_cap_below_committed_fails() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 1_000_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+        ks.total_committed_amount = 3_000_000;
+    })?;
+
+    let result = harness.send(accounts.set_hard_cap_ix(2_000_000, false), &accounts.admin);
+    assert_error_code(result, ErrorCode::InvalidHardCap)?;
+
+    let result = harness.send(accounts.set_hard_cap_ix(500_000, false), &accounts.admin);
+    assert_error_code(result, ErrorCode::InvalidHardCap)?;
+
+    // private commitments are part of the raise the cap limits
+    harness.update_account::<PrivateFundState, _>(&accounts.private_state, |state| {
+        state.committed_amount = 1_000_000;
+    })?;
+    let result = harness.send(accounts.set_hard_cap_ix(3_500_000, false), &accounts.admin);
+    assert_error_code(result, ErrorCode::InvalidHardCap)?;
+    harness.send(accounts.set_hard_cap_ix(4_000_000, false), &accounts.admin)?;
+
+    Ok(())
+}
+
+#[test]
+fn set_hard_cap_fails_while_private_state_is_delegated() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 1_000_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.delegate_account(&accounts.private_state)?;
+
+    let result = harness.send(accounts.set_hard_cap_ix(4_000_000, false), &accounts.admin);
+    assert_error_code(result, ErrorCode::PrivateStateDelegated)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.hard_cap, u64::MAX);
+
+    Ok(())
+}
+
 #[test]
 fn stake_from_treasury_succeeds() -> TestResult {
     let mut harness = TestHarness::new()?;
@@ -124,6 +223,7 @@ fn stake_from_treasury_succeeds() -> TestResult {
         FINAL_RAISE_AMOUNT,
     )?;
 
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     harness.send(accounts.complete_ix(FINAL_RAISE_AMOUNT), &accounts.admin)?;
 
     // Set treasury balance for staking
@@ -179,6 +279,7 @@ fn stake_from_treasury_insufficient_balance_fails() -> TestResult {
         FINAL_RAISE_AMOUNT,
     )?;
 
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     harness.send(accounts.complete_ix(FINAL_RAISE_AMOUNT), &accounts.admin)?;
 
     // трежери меньше чем стейк аккаунт, надо чтобы выполняющий акк был админом
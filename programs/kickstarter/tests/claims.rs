@@ -1,10 +1,10 @@
 mod support;
 
 use anchor_lang::solana_program::program_pack::Pack;
-use kickstarter::state::KickstarterState;
+use kickstarter::{error::ErrorCode, state::{FunderPosition, KickstarterState}};
 use solana_keypair::Keypair;
 use support::{
-    claim_ix, fund_ix, refund_ix, InitConfig, KickstarterAccounts, Pubkey,
+    assert_error_code, claim_ix, derive_funder_position, fund_ix, refund_ix, InitConfig, KickstarterAccounts, Pubkey,
     Signer, TestHarness, TestResult,
 };
 
@@ -42,6 +42,7 @@ fn claim_proportional_distribution_single_user() -> TestResult {
         5_000_000,
     )?;
 
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     harness.send(accounts.complete_ix(5_000_000), &accounts.admin)?;
 
     let ks_state = harness.kickstarter_state(&accounts.kickstarter_pda)?;
@@ -56,6 +57,12 @@ fn claim_proportional_distribution_single_user() -> TestResult {
         &accounts.base_vault,
         &user_base,
     );
+    let result = harness.send(ix_claim.clone(), &user);
+    assert_error_code(result, ErrorCode::PoolNotSeeded)?;
+
+    // seeding is permissionless, the investor does not have to wait for the admin
+    harness.send(accounts.seed_pool_ix(&user.pubkey()), &user)?;
+    harness.svm.expire_blockhash();
     harness.send(ix_claim, &user)?;
 
     let user_base_acc = harness.account(&user_base).unwrap();
@@ -100,6 +107,7 @@ fn refund_returns_funds() -> TestResult {
         500_000,
     )?;
 
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     harness.send(accounts.complete_ix(0), &accounts.admin)?;
 
     let ks_state = harness.kickstarter_state(&accounts.kickstarter_pda)?;
@@ -148,3 +156,232 @@ fn double_start_fails() -> TestResult {
     Ok(())
 }
 
+
+#[test]
+fn claim_follows_vesting_schedule() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    let config = InitConfig {
+        vesting_tge_bps: 2_000,
+        vesting_cliff_seconds: 100,
+        vesting_duration_seconds: 1_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+
+    let user_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(user_quote, accounts.quote_mint, user.pubkey(), 5_000_000)?;
+    harness.send(
+        fund_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, 5_000_000),
+        &user,
+    )?;
+
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+    harness.send(accounts.complete_ix(5_000_000), &accounts.admin)?;
+    harness.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin)?;
+
+    let ks_state = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    let entitlement = ks_state.total_base_tokens_for_investors;
+    let completed_at = ks_state.unix_timestamp_closed.ok_or("completion time missing")?;
+
+    let user_base = Pubkey::new_unique();
+    harness.create_mock_token_account(user_base, accounts.base_mint, user.pubkey())?;
+    let claimed = |harness: &TestHarness| -> TestResult<u64> {
+        let account = harness.account(&user_base).ok_or("token account not found")?;
+        Ok(anchor_spl::token::spl_token::state::Account::unpack(&account.data)?.amount)
+    };
+    let claim = || claim_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.base_vault, &user_base);
+
+    // only the TGE part right after completion
+    harness.send(claim(), &user)?;
+    assert_eq!(claimed(&harness)?, entitlement / 5);
+
+    // halfway through: TGE + half of the linear part
+    harness.warp_to_timestamp(completed_at + 500);
+    harness.send(claim(), &user)?;
+    assert_eq!(claimed(&harness)?, entitlement / 5 + entitlement * 4 / 5 / 2);
+
+    harness.warp_to_timestamp(completed_at + 1_000);
+    harness.send(claim(), &user)?;
+    assert_eq!(claimed(&harness)?, entitlement);
+
+    Ok(())
+}
+
+#[test]
+fn claim_before_cliff_gets_only_tge_part() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    let config = InitConfig {
+        vesting_tge_bps: 1_000,
+        vesting_cliff_seconds: 300,
+        vesting_duration_seconds: 1_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+
+    let user_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(user_quote, accounts.quote_mint, user.pubkey(), 5_000_000)?;
+    harness.send(
+        fund_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, 5_000_000),
+        &user,
+    )?;
+
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+    harness.send(accounts.complete_ix(5_000_000), &accounts.admin)?;
+    harness.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin)?;
+
+    let ks_state = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    let completed_at = ks_state.unix_timestamp_closed.ok_or("completion time missing")?;
+    harness.warp_to_timestamp(completed_at + 299);
+
+    let user_base = Pubkey::new_unique();
+    harness.create_mock_token_account(user_base, accounts.base_mint, user.pubkey())?;
+    harness.send(
+        claim_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.base_vault, &user_base),
+        &user,
+    )?;
+
+    let position = harness.funder_position(&derive_funder_position(&accounts.kickstarter_pda, &user.pubkey()))?;
+    assert_eq!(position.already_claimed_base, ks_state.total_base_tokens_for_investors / 10);
+
+    Ok(())
+}
+
+#[test]
+fn claim_twice_pays_only_once() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    harness.airdrop(&alice.pubkey(), 10_000_000_000)?;
+    harness.airdrop(&bob.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+
+    // two funders, so the vault holds enough to pay a duplicate claim out of bob's share
+    for funder in [&alice, &bob] {
+        let quote = Pubkey::new_unique();
+        harness.set_token_account_balance(quote, accounts.quote_mint, funder.pubkey(), 2_500_000)?;
+        harness.send(
+            fund_ix(&funder.pubkey(), &accounts.kickstarter_pda, &quote, &accounts.quote_vault, 2_500_000),
+            funder,
+        )?;
+    }
+
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+    harness.send(accounts.complete_ix(5_000_000), &accounts.admin)?;
+    harness.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin)?;
+    let share = harness.kickstarter_state(&accounts.kickstarter_pda)?.total_base_tokens_for_investors / 2;
+
+    let balance = |harness: &TestHarness, key: &Pubkey| -> TestResult<u64> {
+        let account = harness.account(key).ok_or("token account not found")?;
+        Ok(anchor_spl::token::spl_token::state::Account::unpack(&account.data)?.amount)
+    };
+
+    let alice_base = Pubkey::new_unique();
+    harness.create_mock_token_account(alice_base, accounts.base_mint, alice.pubkey())?;
+    let alice_claim = || claim_ix(&alice.pubkey(), &accounts.kickstarter_pda, &accounts.base_vault, &alice_base);
+
+    harness.send(alice_claim(), &alice)?;
+    assert_eq!(balance(&harness, &alice_base)?, share);
+
+    harness.svm.expire_blockhash();
+    let result = harness.send(alice_claim(), &alice);
+    assert_error_code(result, ErrorCode::NothingToClaim)?;
+    assert_eq!(balance(&harness, &alice_base)?, share);
+
+    let position = harness.funder_position(&derive_funder_position(&accounts.kickstarter_pda, &alice.pubkey()))?;
+    assert_eq!(position.already_claimed_base, share);
+
+    // bob's share is still in the vault
+    let bob_base = Pubkey::new_unique();
+    harness.create_mock_token_account(bob_base, accounts.base_mint, bob.pubkey())?;
+    harness.send(
+        claim_ix(&bob.pubkey(), &accounts.kickstarter_pda, &accounts.base_vault, &bob_base),
+        &bob,
+    )?;
+    assert_eq!(balance(&harness, &bob_base)?, share);
+
+    Ok(())
+}
+
+#[test]
+fn claim_with_unrepresentable_share_fails_with_math_overflow() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+
+    let user_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(user_quote, accounts.quote_mint, user.pubkey(), 5_000_000)?;
+    harness.send(
+        fund_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, 5_000_000),
+        &user,
+    )?;
+
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+    harness.send(accounts.complete_ix(5_000_000), &accounts.admin)?;
+    harness.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin)?;
+
+    // committed * investor tokens / snapshot no longer fits in u64
+    harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+        ks.total_committed_at_completion = Some(1);
+    })?;
+
+    let user_base = Pubkey::new_unique();
+    harness.create_mock_token_account(user_base, accounts.base_mint, user.pubkey())?;
+    let result = harness.send(
+        claim_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.base_vault, &user_base),
+        &user,
+    );
+    assert_error_code(result, ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
+
+#[test]
+fn refund_with_inconsistent_position_fails_with_math_overflow() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+
+    let user_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(user_quote, accounts.quote_mint, user.pubkey(), 500_000)?;
+    harness.send(
+        fund_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, 500_000),
+        &user,
+    )?;
+
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+    harness.send(accounts.complete_ix(0), &accounts.admin)?;
+
+    let position_key = derive_funder_position(&accounts.kickstarter_pda, &user.pubkey());
+    harness.update_account::<FunderPosition, _>(&position_key, |position| {
+        position.claimed_refund = position.committed_amount + 1;
+    })?;
+
+    let result = harness.send(
+        refund_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.quote_vault, &user_quote),
+        &user,
+    );
+    assert_error_code(result, ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
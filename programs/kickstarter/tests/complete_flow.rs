@@ -1,7 +1,11 @@
 mod support;
 
-use kickstarter::state::KickstarterState;
-use support::{InitConfig, KickstarterAccounts, TestHarness, TestResult};
+use kickstarter::{constants::COMPLETION_GRACE_PERIOD_SECONDS, error::ErrorCode, state::KickstarterState};
+use solana_keypair::Keypair;
+use support::{
+    assert_error_code, fund_ix, InitConfig, KickstarterAccounts, Pubkey, Signer, TestHarness,
+    TestResult,
+};
 
 #[test]
 fn complete_kickstarter_success_path() -> TestResult {
@@ -28,6 +32,7 @@ fn complete_kickstarter_success_path() -> TestResult {
         FINAL_RAISE_AMOUNT,
     )?;
 
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     let ix = accounts.complete_ix(FINAL_RAISE_AMOUNT);
     harness.send(ix, &accounts.admin)?;
 
@@ -54,12 +59,27 @@ fn complete_kickstarter_refund_path() -> TestResult {
         ks.total_committed_amount = 100_000;
     })?;
 
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     let ix = accounts.complete_ix(0);
     harness.send(ix, &accounts.admin)?;
 
     let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
     assert_eq!(decoded.state, KickstarterState::Refunding);
 
+    // a failed raise creates no pool or treasury accounts and cannot seed them
+    for key in [
+        accounts.liquidity_pool,
+        accounts.pool_base_vault,
+        accounts.pool_quote_vault,
+        accounts.lp_mint,
+        accounts.lp_vault,
+        accounts.treasury_vault,
+    ] {
+        assert!(harness.account(&key).is_none());
+    }
+    let result = harness.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin);
+    assert_error_code(result, ErrorCode::InvalidKickstarterState)?;
+
     Ok(())
 }
 
@@ -107,6 +127,7 @@ fn calculated_liquidity_amount_is_set_after_complete() -> TestResult {
         FINAL_RAISE_AMOUNT,
     )?;
 
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     let ix = accounts.complete_ix(FINAL_RAISE_AMOUNT);
     harness.send(ix, &accounts.admin)?;
 
@@ -118,3 +139,167 @@ fn calculated_liquidity_amount_is_set_after_complete() -> TestResult {
     Ok(())
 }
 
+
+#[test]
+fn start_sets_funding_deadline_from_seconds_for_launch() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+
+    let config = InitConfig {
+        seconds_for_launch: 7_200,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    let started = decoded.unix_timestamp_started.ok_or("start time missing")?;
+    assert_eq!(decoded.unix_timestamp_closed, Some(started + 7_200));
+
+    Ok(())
+}
+
+#[test]
+fn complete_before_deadline_fails() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+
+    let deadline = harness
+        .kickstarter_state(&accounts.kickstarter_pda)?
+        .unix_timestamp_closed
+        .ok_or("deadline missing")?;
+    harness.warp_to_timestamp(deadline - 1);
+
+    let result = harness.send(accounts.complete_ix(0), &accounts.admin);
+    assert_error_code(result, ErrorCode::TooEarlyToCompleteKickstarter)?;
+
+    harness.warp_to_timestamp(deadline);
+    harness.send(accounts.complete_ix(0), &accounts.admin)?;
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.state, KickstarterState::Refunding);
+
+    Ok(())
+}
+
+#[test]
+fn fund_after_deadline_fails() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+        ks.hard_cap = u64::MAX;
+    })?;
+
+    let user_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(user_quote, accounts.quote_mint, user.pubkey(), 2_000_000)?;
+    let fund = |amount| fund_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, amount);
+
+    harness.send(fund(1_000_000), &user)?;
+
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+    let result = harness.send(fund(1_000_000), &user);
+    assert_error_code(result, ErrorCode::FundingTimeIsOut)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.total_committed_amount, 1_000_000);
+
+    Ok(())
+}
+
+#[test]
+fn anyone_can_trigger_refunds_for_failed_raise() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let stranger = Keypair::new();
+    harness.airdrop(&stranger.pubkey(), 10_000_000_000)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 500_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+        ks.total_committed_amount = 100_000;
+    })?;
+
+    let result = harness.send(accounts.trigger_refunds_ix(&stranger.pubkey()), &stranger);
+    assert_error_code(result, ErrorCode::TooEarlyToCompleteKickstarter)?;
+
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+    harness.send(accounts.trigger_refunds_ix(&stranger.pubkey()), &stranger)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.state, KickstarterState::Refunding);
+
+    Ok(())
+}
+
+#[test]
+fn successful_raise_falls_back_to_refunds_after_grace_period() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let stranger = Keypair::new();
+    harness.airdrop(&stranger.pubkey(), 10_000_000_000)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 500_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+        ks.total_committed_amount = 1_000_000;
+    })?;
+
+    // the minimum is met, so the admin still gets the grace period
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+    let result = harness.send(accounts.trigger_refunds_ix(&stranger.pubkey()), &stranger);
+    assert_error_code(result, ErrorCode::RaiseCanStillBeCompleted)?;
+
+    let deadline = harness.unix_timestamp();
+    harness.warp_to_timestamp(deadline + COMPLETION_GRACE_PERIOD_SECONDS);
+    harness.send(accounts.trigger_refunds_ix(&stranger.pubkey()), &stranger)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.state, KickstarterState::Refunding);
+    assert_eq!(decoded.final_raise_amount, None);
+
+    let result = harness.send(accounts.complete_ix(1_000_000), &accounts.admin);
+    assert_error_code(result, ErrorCode::InvalidKickstarterState)?;
+
+    Ok(())
+}
+
+#[test]
+fn complete_after_grace_period_refunds() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 500_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+        ks.total_committed_amount = 1_000_000;
+    })?;
+
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+    let deadline = harness.unix_timestamp();
+    harness.warp_to_timestamp(deadline + COMPLETION_GRACE_PERIOD_SECONDS);
+    harness.send(accounts.complete_ix(1_000_000), &accounts.admin)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.state, KickstarterState::Refunding);
+
+    Ok(())
+}
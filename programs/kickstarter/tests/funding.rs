@@ -0,0 +1,251 @@
+mod support;
+
+use kickstarter::{error::ErrorCode, state::{FunderPosition, KickstarterState, PrivateFundState}};
+use solana_keypair::Keypair;
+use support::{
+    assert_error_code, derive_funder_position, fund_ix, withdraw_commitment_ix, InitConfig,
+    KickstarterAccounts, Pubkey, Signer, TestHarness, TestResult,
+};
+
+#[test]
+fn deposit_crossing_hard_cap_is_partially_accepted() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 1_000_000,
+        hard_cap: 3_000_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+
+    let user_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(user_quote, accounts.quote_mint, user.pubkey(), 5_000_000)?;
+    let fund = |amount| fund_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, amount);
+
+    harness.send(fund(2_000_000), &user)?;
+    // only 1_000_000 of this fits under the cap
+    harness.send(fund(2_000_000), &user)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.total_committed_amount, 3_000_000);
+    assert_eq!(harness.token_balance(&user_quote)?, 2_000_000);
+    assert_eq!(harness.token_balance(&accounts.quote_vault)?, 3_000_000);
+
+    let position = harness.funder_position(&derive_funder_position(&accounts.kickstarter_pda, &user.pubkey()))?;
+    assert_eq!(position.committed_amount, 3_000_000);
+
+    // the raise stays open until the deadline
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert!(decoded.unix_timestamp_closed > Some(harness.unix_timestamp()));
+
+    harness.svm.expire_blockhash();
+    let result = harness.send(fund(1), &user);
+    assert_error_code(result, ErrorCode::OverHardcapLimit)?;
+
+    Ok(())
+}
+
+#[test]
+fn reaching_hard_cap_closes_raise_early() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 1_000_000,
+        hard_cap: 3_000_000,
+        close_on_hard_cap: true,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+
+    let user_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(user_quote, accounts.quote_mint, user.pubkey(), 5_000_000)?;
+    harness.send(
+        fund_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, 5_000_000),
+        &user,
+    )?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.total_committed_amount, 3_000_000);
+    assert_eq!(decoded.unix_timestamp_closed, Some(harness.unix_timestamp()));
+
+    // no need to wait for seconds_for_launch
+    harness.send(accounts.complete_ix(3_000_000), &accounts.admin)?;
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.state, KickstarterState::Complete);
+
+    Ok(())
+}
+
+#[test]
+fn private_commitments_count_toward_hard_cap() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 1_000_000,
+        hard_cap: 3_000_000,
+        close_on_hard_cap: true,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.update_account::<PrivateFundState, _>(&accounts.private_state, |state| {
+        state.committed_amount = 2_000_000;
+    })?;
+
+    let user_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(user_quote, accounts.quote_mint, user.pubkey(), 5_000_000)?;
+    harness.send(
+        fund_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, 5_000_000),
+        &user,
+    )?;
+
+    // only the 1_000_000 left next to the private round is taken, and that closes the raise
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.total_committed_amount, 1_000_000);
+    assert_eq!(harness.token_balance(&user_quote)?, 4_000_000);
+    assert_eq!(decoded.unix_timestamp_closed, Some(harness.unix_timestamp()));
+
+    Ok(())
+}
+
+#[test]
+fn fund_fails_while_private_state_is_delegated() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 1_000_000,
+        hard_cap: 3_000_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    // a stale base-layer total, the live one is on the rollup
+    harness.update_account::<PrivateFundState, _>(&accounts.private_state, |state| {
+        state.committed_amount = 2_000_000;
+    })?;
+    harness.delegate_account(&accounts.private_state)?;
+
+    let user_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(user_quote, accounts.quote_mint, user.pubkey(), 5_000_000)?;
+    let result = harness.send(
+        fund_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, 3_000_000),
+        &user,
+    );
+    assert_error_code(result, ErrorCode::PrivateStateDelegated)?;
+    assert_eq!(harness.token_balance(&user_quote)?, 5_000_000);
+
+    Ok(())
+}
+
+#[test]
+fn withdraw_commitment_returns_funds_while_live() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+
+    let user_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(user_quote, accounts.quote_mint, user.pubkey(), 5_000_000)?;
+    harness.send(
+        fund_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, 5_000_000),
+        &user,
+    )?;
+
+    let withdraw = |amount| withdraw_commitment_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, amount);
+    harness.send(withdraw(2_000_000), &user)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.total_committed_amount, 3_000_000);
+    let position = harness.funder_position(&derive_funder_position(&accounts.kickstarter_pda, &user.pubkey()))?;
+    assert_eq!(position.committed_amount, 3_000_000);
+    assert_eq!(harness.token_balance(&user_quote)?, 2_000_000);
+    assert_eq!(harness.token_balance(&accounts.quote_vault)?, 3_000_000);
+
+    let result = harness.send(withdraw(3_000_001), &user);
+    assert_error_code(result, ErrorCode::InsufficientCommitment)?;
+
+    Ok(())
+}
+
+#[test]
+fn withdraw_commitment_is_blocked_inside_cutoff_window() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    let config = InitConfig {
+        seconds_for_launch: 3_600,
+        withdrawal_cutoff_seconds: 600,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+
+    let user_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(user_quote, accounts.quote_mint, user.pubkey(), 5_000_000)?;
+    harness.send(
+        fund_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, 5_000_000),
+        &user,
+    )?;
+
+    let deadline = harness
+        .kickstarter_state(&accounts.kickstarter_pda)?
+        .unix_timestamp_closed
+        .ok_or("deadline missing")?;
+    harness.warp_to_timestamp(deadline - 600);
+
+    let result = harness.send(
+        withdraw_commitment_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, 1_000_000),
+        &user,
+    );
+    assert_error_code(result, ErrorCode::WithdrawalWindowClosed)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.total_committed_amount, 5_000_000);
+
+    Ok(())
+}
+
+#[test]
+fn fund_overflowing_position_fails_with_math_overflow() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+
+    let user_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(user_quote, accounts.quote_mint, user.pubkey(), 2_000_000)?;
+    let fund = |amount| fund_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.quote_vault, amount);
+    harness.send(fund(1_000_000), &user)?;
+
+    let position_key = derive_funder_position(&accounts.kickstarter_pda, &user.pubkey());
+    harness.update_account::<FunderPosition, _>(&position_key, |position| {
+        position.committed_amount = u64::MAX;
+    })?;
+
+    let result = harness.send(fund(1_000_000), &user);
+    assert_error_code(result, ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
 mod support;
 
-use kickstarter::state::KickstarterState;
-use support::{InitConfig, KickstarterAccounts, Signer, TestHarness, TestResult, to_anchor_pubkey};
+use kickstarter::{error::ErrorCode, state::KickstarterState};
+use support::{
+    assert_error_code, to_anchor_pubkey, InitConfig, KickstarterAccounts, Signer, TestHarness,
+    TestResult,
+};
 
 #[test]
 fn initialize_kickstarter_sets_initial_state() -> TestResult {
@@ -44,3 +47,62 @@ fn start_kickstarter_succeeds() -> TestResult {
     Ok(())
 }
 
+
+#[test]
+fn initialize_kickstarter_stores_hard_cap() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 1_000_000,
+        hard_cap: 5_000_000,
+        close_on_hard_cap: true,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.hard_cap, 5_000_000);
+    assert!(decoded.close_on_hard_cap);
+
+    Ok(())
+}
+
+#[test]
+fn initialize_kickstarter_with_hard_cap_below_minimum_fails() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 1_000_000,
+        hard_cap: 999_999,
+        ..Default::default()
+    };
+    let result = harness.send(accounts.initialize_ix(config), &accounts.admin);
+    assert_error_code(result, ErrorCode::InvalidHardCap)?;
+
+    Ok(())
+}
+
+#[test]
+fn initialize_kickstarter_with_liquidity_ratio_above_100_percent_fails() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+
+    let config = InitConfig {
+        liquidity_bps: 10_001,
+        ..Default::default()
+    };
+    let result = harness.send(accounts.initialize_ix(config), &accounts.admin);
+    assert_error_code(result, ErrorCode::InvalidLiquidityConfig)?;
+
+    let config = InitConfig {
+        base_tokens_for_liquidity_bps: 10_001,
+        ..Default::default()
+    };
+    harness.svm.expire_blockhash();
+    let result = harness.send(accounts.initialize_ix(config), &accounts.admin);
+    assert_error_code(result, ErrorCode::InvalidLiquidityConfig)?;
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+mod support;
+
+use anchor_lang::solana_program::program_pack::Pack;
+use kickstarter::{amm, constants::POOL_FEE_BPS, error::ErrorCode};
+use solana_keypair::Keypair;
+use support::{
+    assert_error_code, to_anchor_pubkey, InitConfig, KickstarterAccounts, Pubkey, Signer,
+    TestHarness, TestResult,
+};
+
+const FINAL_RAISE_AMOUNT: u64 = 1_000_000;
+
+fn complete_raise(harness: &mut TestHarness, accounts: &KickstarterAccounts) -> TestResult {
+    let config = InitConfig {
+        minimum_raise_amount: 500_000,
+        ..Default::default()
+    };
+    harness.complete_raise(accounts, config, vec![], FINAL_RAISE_AMOUNT)?;
+    Ok(())
+}
+
+#[test]
+fn complete_seeds_program_owned_pool() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    complete_raise(&mut harness, &accounts)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    let quote_liquidity = decoded.calculated_liquidity_amount.unwrap();
+    let base_liquidity = decoded.calculated_base_tokens_for_liquidity.unwrap();
+    assert_eq!(harness.token_balance(&accounts.pool_quote_vault)?, quote_liquidity);
+    assert_eq!(harness.token_balance(&accounts.pool_base_vault)?, base_liquidity);
+
+    let lp_vault = harness.account(&accounts.lp_vault).ok_or("lp vault not found")?;
+    let lp_vault = anchor_spl::token::spl_token::state::Account::unpack(&lp_vault.data)?;
+    assert_eq!(lp_vault.owner, to_anchor_pubkey(&accounts.kickstarter_pda));
+    assert_eq!(lp_vault.mint, to_anchor_pubkey(&accounts.lp_mint));
+    assert_eq!(lp_vault.amount, amm::initial_lp_amount(base_liquidity, quote_liquidity)?);
+
+    Ok(())
+}
+
+#[test]
+fn swap_against_seeded_pool() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    complete_raise(&mut harness, &accounts)?;
+
+    let trader = Keypair::new();
+    harness.airdrop(&trader.pubkey(), 1_000_000_000)?;
+    let trader_base = Pubkey::new_unique();
+    harness.create_mock_token_account(trader_base, accounts.base_mint, trader.pubkey())?;
+    let trader_quote = Pubkey::new_unique();
+    const AMOUNT_IN: u64 = 10_000;
+    harness.set_token_account_balance(trader_quote, accounts.quote_mint, trader.pubkey(), AMOUNT_IN)?;
+
+    let reserve_quote = harness.token_balance(&accounts.pool_quote_vault)?;
+    let reserve_base = harness.token_balance(&accounts.pool_base_vault)?;
+    let expected_out = amm::swap_output(AMOUNT_IN, reserve_quote, reserve_base, POOL_FEE_BPS)?;
+
+    let result = harness.send(
+        accounts.swap_ix(&trader.pubkey(), trader_base, trader_quote, AMOUNT_IN, expected_out + 1, false),
+        &trader,
+    );
+    assert_error_code(result, ErrorCode::SlippageExceeded)?;
+
+    harness.send(
+        accounts.swap_ix(&trader.pubkey(), trader_base, trader_quote, AMOUNT_IN, expected_out, false),
+        &trader,
+    )?;
+    assert_eq!(harness.token_balance(&trader_base)?, expected_out);
+    assert_eq!(harness.token_balance(&trader_quote)?, 0);
+    assert_eq!(harness.token_balance(&accounts.pool_quote_vault)?, reserve_quote + AMOUNT_IN);
+    assert_eq!(harness.token_balance(&accounts.pool_base_vault)?, reserve_base - expected_out);
+
+    Ok(())
+}
+
+#[test]
+fn locked_liquidity_withdrawable_by_admin_after_unlock() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    complete_raise(&mut harness, &accounts)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    let unlock_timestamp = decoded.liquidity_unlock_timestamp.unwrap();
+    assert_eq!(
+        unlock_timestamp,
+        harness.unix_timestamp() + decoded.liquidity_lock_seconds as i64
+    );
+
+    let admin_base = Pubkey::new_unique();
+    harness.create_mock_token_account(admin_base, accounts.base_mint, accounts.admin.pubkey())?;
+    let admin_quote = Pubkey::new_unique();
+    harness.create_mock_token_account(admin_quote, accounts.quote_mint, accounts.admin.pubkey())?;
+
+    let result = harness.send(
+        accounts.withdraw_locked_liquidity_ix(&accounts.admin.pubkey(), admin_base, admin_quote),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::LiquidityStillLocked)?;
+
+    harness.warp_to_timestamp(unlock_timestamp);
+
+    let outsider = Keypair::new();
+    harness.airdrop(&outsider.pubkey(), 1_000_000_000)?;
+    let result = harness.send(
+        accounts.withdraw_locked_liquidity_ix(&outsider.pubkey(), admin_base, admin_quote),
+        &outsider,
+    );
+    assert_error_code(result, ErrorCode::InvalidAdmin)?;
+
+    let reserve_base = harness.token_balance(&accounts.pool_base_vault)?;
+    let reserve_quote = harness.token_balance(&accounts.pool_quote_vault)?;
+    harness.send(
+        accounts.withdraw_locked_liquidity_ix(&accounts.admin.pubkey(), admin_base, admin_quote),
+        &accounts.admin,
+    )?;
+    assert_eq!(harness.token_balance(&accounts.lp_vault)?, 0);
+    assert_eq!(harness.token_balance(&admin_base)?, reserve_base);
+    assert_eq!(harness.token_balance(&admin_quote)?, reserve_quote);
+    assert_eq!(harness.token_balance(&accounts.pool_base_vault)?, 0);
+    assert_eq!(harness.token_balance(&accounts.pool_quote_vault)?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn derived_base_liquidity_opens_pool_at_sale_price() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let config = InitConfig {
+        minimum_raise_amount: 500_000,
+        liquidity_bps: 3_500,
+        base_tokens_for_liquidity_bps: 0,
+        ..Default::default()
+    };
+    let total_base_tokens_for_investors = config.total_base_tokens_for_investors;
+    harness.complete_raise(&accounts, config, vec![], FINAL_RAISE_AMOUNT)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    let quote_liquidity = decoded.calculated_liquidity_amount.unwrap();
+    let base_liquidity = decoded.calculated_base_tokens_for_liquidity.unwrap();
+    assert_eq!(quote_liquidity, FINAL_RAISE_AMOUNT * 3_500 / 10_000);
+    assert_eq!(
+        base_liquidity as u128,
+        quote_liquidity as u128 * total_base_tokens_for_investors as u128 / FINAL_RAISE_AMOUNT as u128
+    );
+
+    let pool_price = quote_liquidity as u128 * 1_000_000_000_000 / base_liquidity as u128;
+    assert_eq!(pool_price as u64, decoded.initial_token_price.unwrap());
+    assert_eq!(harness.token_balance(&accounts.pool_base_vault)?, base_liquidity);
+
+    Ok(())
+}
+
+#[test]
+fn one_sided_pool_reserve_withdrawable_after_unlock() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let config = InitConfig {
+        minimum_raise_amount: 500_000,
+        liquidity_bps: 0,
+        ..Default::default()
+    };
+    harness.complete_raise(&accounts, config, vec![], FINAL_RAISE_AMOUNT)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    let base_liquidity = decoded.calculated_base_tokens_for_liquidity.unwrap();
+    assert!(base_liquidity > 0);
+    assert_eq!(harness.token_balance(&accounts.pool_quote_vault)?, 0);
+    assert_eq!(harness.token_balance(&accounts.lp_vault)?, 0);
+
+    let admin_base = Pubkey::new_unique();
+    harness.create_mock_token_account(admin_base, accounts.base_mint, accounts.admin.pubkey())?;
+    let admin_quote = Pubkey::new_unique();
+    harness.create_mock_token_account(admin_quote, accounts.quote_mint, accounts.admin.pubkey())?;
+
+    harness.warp_to_timestamp(decoded.liquidity_unlock_timestamp.unwrap());
+    harness.send(
+        accounts.withdraw_locked_liquidity_ix(&accounts.admin.pubkey(), admin_base, admin_quote),
+        &accounts.admin,
+    )?;
+    assert_eq!(harness.token_balance(&admin_base)?, base_liquidity);
+    assert_eq!(harness.token_balance(&accounts.pool_base_vault)?, 0);
+
+    Ok(())
+}
@@ -1,9 +1,10 @@
 mod support;
 
+use kickstarter::merkle_tree::compute_proof;
 use solana_keypair::Keypair;
 use support::{
-    fund_private_ix, private_claim_ix, start_private_round_ix,
-    finalize_private_round_ix, InitConfig, KickstarterAccounts,
+    deposit_private, fund_private_ix, private_claim_ix, start_private_round_ix,
+    finalize_private_round_ix, commitment_leaf, InitConfig, KickstarterAccounts,
     Pubkey, Signer, TestHarness, TestResult,
 };
 
@@ -27,12 +28,14 @@ fn load_test_50_private_investors() -> TestResult {
 
     let start_time = std::time::Instant::now();
     let mut total_committed = 0u64;
+    let mut leaves = Vec::new();
 
     // Пакетное приватное финансирование
     for (i, investor) in investors.iter().enumerate() {
         let amount = 100_000 + (i as u64 * 10_000); // Разные суммы
         let salt = [i as u8; 32]; // Уникальный salt для каждого
 
+        deposit_private(&mut harness, &accounts, investor, amount)?;
         let ix = fund_private_ix(
             &investor.pubkey(),
             &accounts.kickstarter_pda,
@@ -43,6 +46,7 @@ fn load_test_50_private_investors() -> TestResult {
         harness.send(ix, investor)?;
 
         total_committed += amount;
+        leaves.push(commitment_leaf(&investor.pubkey(), amount, salt));
 
         if (i + 1) % 10 == 0 {
             println!("✅ Processed {} investors, total committed: {} USDC", i + 1, total_committed / 1_000_000);
@@ -54,16 +58,20 @@ fn load_test_50_private_investors() -> TestResult {
 
     // Финализируем приватный раунд
     let final_private_state = harness.private_state(&accounts.private_state)?;
-    harness.send(finalize_private_round_ix(
+    harness.send_all(finalize_private_round_ix(
         &accounts.admin.pubkey(),
         &accounts.kickstarter_pda,
         &accounts.private_state,
+        &accounts.private_quote_vault,
+        &accounts.quote_vault,
         final_private_state.commitments_root,
         final_private_state.committed_amount,
-        [0u8; 64],
+        &accounts.attester,
     ), &accounts.admin)?;
 
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     harness.send(accounts.complete_ix(total_committed), &accounts.admin)?;
+    harness.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin)?;
 
     // Пакетное приватное claim'инг
     let claim_start_time = std::time::Instant::now();
@@ -83,6 +91,8 @@ fn load_test_50_private_investors() -> TestResult {
             &user_base,
             amount,
             salt,
+            i as u32,
+            compute_proof(&leaves, i),
         );
         harness.send(ix, investor)?;
     }
@@ -1,6 +1,40 @@
 mod support;
 
-use support::{InitConfig, KickstarterAccounts, Pubkey, Signer, TestHarness, TestResult};
+use kickstarter::{
+    constants::MAX_PERFORMANCE_PACKAGES, error::ErrorCode, state::PerformanceTokenRelease,
+};
+use solana_instruction::Instruction;
+use solana_keypair::Keypair;
+use support::{
+    assert_error_code, InitConfig, KickstarterAccounts, Pubkey, Signer, TestHarness, TestResult,
+};
+
+/// Oracle exponent matching the 12 `token_price_decimals` of `initial_token_price`,
+/// so feed prices compare 1:1 with targets.
+const PRICE_EXPONENT: i32 = -12;
+const TWAP_WINDOW_SECONDS: i64 = 3_200;
+/// Spacing `record_price` enforces for a 3_200 second window.
+const OBSERVATION_INTERVAL: i64 = 100;
+
+/// Cranks `record_price` with each price in turn, one observation interval apart.
+fn record_prices(harness: &mut TestHarness, accounts: &KickstarterAccounts, prices: &[u64]) -> TestResult {
+    let cranker = Keypair::new();
+    harness.airdrop(&cranker.pubkey(), 1_000_000_000)?;
+    for &price in prices {
+        let now = harness.unix_timestamp() + OBSERVATION_INTERVAL;
+        harness.warp_to_timestamp(now);
+        harness.svm.expire_blockhash();
+        harness.set_price_feed(accounts.price_feed, price as i64, 0, PRICE_EXPONENT, now)?;
+        harness.send(accounts.record_price_ix(&cranker.pubkey()), &cranker)?;
+    }
+    Ok(())
+}
+
+/// Enough observations at `price` to cover the TWAP window.
+fn hold_price(harness: &mut TestHarness, accounts: &KickstarterAccounts, price: u64) -> TestResult {
+    let steps = (TWAP_WINDOW_SECONDS / OBSERVATION_INTERVAL + 1) as usize;
+    record_prices(harness, accounts, &vec![price; steps])
+}
 
 #[test]
 fn performance_packages_flow() -> TestResult {
@@ -18,10 +52,10 @@ fn performance_packages_flow() -> TestResult {
     };
     harness.send(accounts.initialize_ix(config), &accounts.admin)?;
 
-    let ix_config = accounts.configure_performance_package_ix(0, 2, PACKAGE_ALLOCATION);
+    let ix_config = accounts.configure_performance_package_ix(0, 20_000, PACKAGE_ALLOCATION, accounts.admin.pubkey(), 0, false);
     harness.send(ix_config, &accounts.admin)?;
 
-    let ix_config_over = accounts.configure_performance_package_ix(1, 4, PERFORMANCE_POOL);
+    let ix_config_over = accounts.configure_performance_package_ix(1, 40_000, PERFORMANCE_POOL, accounts.admin.pubkey(), 0, true);
     let result = harness.send(ix_config_over, &accounts.admin);
     assert!(result.is_err(), "over allocation must fail");
 
@@ -38,6 +72,7 @@ fn performance_packages_flow() -> TestResult {
         FINAL_RAISE_AMOUNT,
     )?;
 
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     harness.send(accounts.complete_ix(FINAL_RAISE_AMOUNT), &accounts.admin)?;
 
     harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
@@ -48,17 +83,18 @@ fn performance_packages_flow() -> TestResult {
     let initial_price = decoded.initial_token_price.unwrap();
     let target_price_2x = initial_price * 2;
 
-    let ix_unlock = accounts.unlock_performance_package_ix(0, target_price_2x);
+    hold_price(&mut harness, &accounts, target_price_2x)?;
+    let ix_unlock = accounts.unlock_performance_package_ix(0);
     harness.send(ix_unlock, &accounts.admin)?;
 
     let recipient_base = Pubkey::new_unique();
     harness.create_mock_token_account(recipient_base, accounts.base_mint, accounts.admin.pubkey())?;
 
-    let ix_claim = accounts.claim_performance_package_ix(0, recipient_base);
+    let ix_claim = accounts.claim_performance_package_ix(&accounts.admin.pubkey(), 0, recipient_base);
     harness.send(ix_claim, &accounts.admin)?;
 
-    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
-    assert!(decoded.performance_packages[0].is_claimed);
+    let package = harness.performance_package(&accounts.performance_package_pda(0))?;
+    assert_eq!(package.claimed_amount, PACKAGE_ALLOCATION);
 
     Ok(())
 }
@@ -78,14 +114,17 @@ fn performance_package_multiplier_works() -> TestResult {
     };
     harness.send(accounts.initialize_ix(config), &accounts.admin)?;
 
-    harness.send(accounts.configure_performance_package_ix(0, 2, PACKAGE_ALLOCATION), &accounts.admin)?;
-    harness.send(accounts.configure_performance_package_ix(1, 4, PACKAGE_ALLOCATION), &accounts.admin)?;
-    harness.send(accounts.configure_performance_package_ix(2, 8, PACKAGE_ALLOCATION), &accounts.admin)?;
+    harness.send(accounts.configure_performance_package_ix(0, 20_000, PACKAGE_ALLOCATION, accounts.admin.pubkey(), 0, false), &accounts.admin)?;
+    harness.send(accounts.configure_performance_package_ix(1, 40_000, PACKAGE_ALLOCATION, accounts.admin.pubkey(), 0, true), &accounts.admin)?;
+    harness.send(accounts.configure_performance_package_ix(2, 80_000, PACKAGE_ALLOCATION, accounts.admin.pubkey(), 0, true), &accounts.admin)?;
 
+    for (index, multiplier_bps) in [(0, 20_000), (1, 40_000), (2, 80_000)] {
+        let package = harness.performance_package(&accounts.performance_package_pda(index))?;
+        assert_eq!(package.multiplier_bps, multiplier_bps);
+        assert_eq!(package.index, index);
+    }
     let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
-    assert_eq!(decoded.performance_packages[0].multiplier, 2);
-    assert_eq!(decoded.performance_packages[1].multiplier, 4);
-    assert_eq!(decoded.performance_packages[2].multiplier, 8);
+    assert_eq!(decoded.performance_package_count, 3);
 
     Ok(())
 }
@@ -105,7 +144,7 @@ fn performance_package_price_target_validation() -> TestResult {
         ..Default::default()
     };
     harness.send(accounts.initialize_ix(config), &accounts.admin)?;
-    harness.send(accounts.configure_performance_package_ix(0, 2, PACKAGE_ALLOCATION), &accounts.admin)?;
+    harness.send(accounts.configure_performance_package_ix(0, 20_000, PACKAGE_ALLOCATION, accounts.admin.pubkey(), 0, false), &accounts.admin)?;
     harness.send(accounts.start_ix(), &accounts.admin)?;
 
     harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
@@ -118,16 +157,458 @@ fn performance_package_price_target_validation() -> TestResult {
         FINAL_RAISE_AMOUNT,
     )?;
 
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     harness.send(accounts.complete_ix(FINAL_RAISE_AMOUNT), &accounts.admin)?;
 
     let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
     let initial_price = decoded.initial_token_price.unwrap();
     let below_target = initial_price + 1;
 
-    let ix_unlock = accounts.unlock_performance_package_ix(0, below_target);
+    hold_price(&mut harness, &accounts, below_target)?;
+    let ix_unlock = accounts.unlock_performance_package_ix(0);
     let result = harness.send(ix_unlock, &accounts.admin);
-    assert!(result.is_err(), "unlock should fail when price is below target");
+    assert_error_code(result, ErrorCode::PriceTargetNotReached)?;
+
+    Ok(())
+}
+
+const COMPLETED_PACKAGE_ALLOCATION: u64 = 500_000_000_000;
+
+/// Completes a raise with one 2x package configured and returns its target price.
+fn complete_with_package(
+    harness: &mut TestHarness,
+    accounts: &KickstarterAccounts,
+    recipient: Pubkey,
+    vesting_duration_seconds: u32,
+) -> TestResult<u64> {
+    let package = accounts.configure_performance_package_ix(
+        0,
+        20_000,
+        COMPLETED_PACKAGE_ALLOCATION,
+        recipient,
+        vesting_duration_seconds,
+        false,
+    );
+    let initial_price = complete_with_packages(harness, accounts, vec![package])?;
+    Ok(initial_price * 2)
+}
+
+/// Completes a raise after sending the given package configurations, returns
+/// the initial token price.
+fn complete_with_packages(
+    harness: &mut TestHarness,
+    accounts: &KickstarterAccounts,
+    packages: Vec<Instruction>,
+) -> TestResult<u64> {
+    let config = InitConfig {
+        minimum_raise_amount: 500_000,
+        performance_pool_base_tokens: 2_000_000_000_000,
+        ..Default::default()
+    };
+    harness.complete_raise(accounts, config, packages, 1_000_000)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    Ok(decoded.initial_token_price.unwrap())
+}
+
+#[test]
+fn unlock_requires_price_to_hold_over_window() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let target_price = complete_with_package(&mut harness, &accounts, accounts.admin.pubkey(), 0)?;
+
+    let result = harness.send(accounts.unlock_performance_package_ix(0), &accounts.admin);
+    assert!(result.is_err(), "unlock needs recorded prices");
+
+    record_prices(&mut harness, &accounts, &[target_price; 10])?;
+    let result = harness.send(accounts.unlock_performance_package_ix(0), &accounts.admin);
+    assert_error_code(result, ErrorCode::InsufficientPriceHistory)?;
+
+    // a single observation far above the target does not lift the average
+    let mut prices = vec![target_price / 2; 32];
+    prices.push(target_price * 10);
+    prices.push(target_price / 2);
+    record_prices(&mut harness, &accounts, &prices)?;
+    harness.svm.expire_blockhash();
+    let result = harness.send(accounts.unlock_performance_package_ix(0), &accounts.admin);
+    assert_error_code(result, ErrorCode::PriceTargetNotReached)?;
+
+    hold_price(&mut harness, &accounts, target_price)?;
+    harness.svm.expire_blockhash();
+    harness.send(accounts.unlock_performance_package_ix(0), &accounts.admin)?;
+
+    let package = harness.performance_package(&accounts.performance_package_pda(0))?;
+    assert!(package.is_unlocked);
+
+    Ok(())
+}
+
+#[test]
+fn unlock_rejects_stale_price_observations() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let target_price = complete_with_package(&mut harness, &accounts, accounts.admin.pubkey(), 0)?;
+
+    hold_price(&mut harness, &accounts, target_price)?;
+    // the cranks stopped more than two intervals ago
+    harness.warp_to_timestamp(harness.unix_timestamp() + 2 * OBSERVATION_INTERVAL + 1);
+    let result = harness.send(accounts.unlock_performance_package_ix(0), &accounts.admin);
+    assert_error_code(result, ErrorCode::StalePriceObservations)?;
+
+    // the uncovered gap counts as zero, so the window has to be refilled
+    hold_price(&mut harness, &accounts, target_price)?;
+    harness.send(accounts.unlock_performance_package_ix(0), &accounts.admin)?;
+
+    Ok(())
+}
+
+#[test]
+fn record_price_rejects_observation_within_interval() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let target_price = complete_with_package(&mut harness, &accounts, accounts.admin.pubkey(), 0)?;
+
+    record_prices(&mut harness, &accounts, &[target_price])?;
+
+    let now = harness.unix_timestamp() + OBSERVATION_INTERVAL - 1;
+    harness.warp_to_timestamp(now);
+    harness.set_price_feed(accounts.price_feed, target_price as i64, 0, PRICE_EXPONENT, now)?;
+    let result = harness.send(accounts.record_price_ix(&accounts.admin.pubkey()), &accounts.admin);
+    assert_error_code(result, ErrorCode::PriceObservationTooSoon)?;
+
+    Ok(())
+}
+
+#[test]
+fn record_price_rejects_stale_oracle_price() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let target_price = complete_with_package(&mut harness, &accounts, accounts.admin.pubkey(), 0)?;
+
+    let published_at = harness.unix_timestamp() - 61;
+    harness.set_price_feed(accounts.price_feed, target_price as i64, 0, PRICE_EXPONENT, published_at)?;
+
+    let result = harness.send(accounts.record_price_ix(&accounts.admin.pubkey()), &accounts.admin);
+    assert_error_code(result, ErrorCode::StalePrice)?;
+
+    Ok(())
+}
+
+#[test]
+fn record_price_rejects_wide_oracle_confidence() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let target_price = complete_with_package(&mut harness, &accounts, accounts.admin.pubkey(), 0)?;
+
+    // 2% of the price against the 1% allowed by InitConfig
+    let conf = target_price / 50;
+    let now = harness.unix_timestamp();
+    harness.set_price_feed(accounts.price_feed, target_price as i64, conf, PRICE_EXPONENT, now)?;
+
+    let result = harness.send(accounts.record_price_ix(&accounts.admin.pubkey()), &accounts.admin);
+    assert_error_code(result, ErrorCode::PriceConfidenceTooWide)?;
+
+    Ok(())
+}
+
+#[test]
+fn record_price_rejects_feed_not_owned_by_oracle() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let target_price = complete_with_package(&mut harness, &accounts, accounts.admin.pubkey(), 0)?;
+
+    let now = harness.unix_timestamp();
+    harness.set_price_feed(accounts.price_feed, target_price as i64, 0, PRICE_EXPONENT, now)?;
+    let mut feed = harness.account(&accounts.price_feed).ok_or("price feed not found")?;
+    feed.owner = accounts.admin.pubkey();
+    harness.svm.set_account(accounts.price_feed, feed).map_err(|err| format!("{err:?}"))?;
+
+    let result = harness.send(accounts.record_price_ix(&accounts.admin.pubkey()), &accounts.admin);
+    assert_error_code(result, ErrorCode::InvalidPriceFeed)?;
+
+    Ok(())
+}
+
+#[test]
+fn record_price_rejects_feed_for_another_price_id() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let target_price = complete_with_package(&mut harness, &accounts, accounts.admin.pubkey(), 0)?;
+
+    let now = harness.unix_timestamp();
+    harness.set_price_feed(accounts.price_feed, target_price as i64, 0, PRICE_EXPONENT, now)?;
+    let mut feed = harness.account(&accounts.price_feed).ok_or("price feed not found")?;
+    // feed_id follows discriminator, write_authority and verification_level
+    feed.data[41..73].copy_from_slice(&[9u8; 32]);
+    harness.svm.set_account(accounts.price_feed, feed).map_err(|err| format!("{err:?}"))?;
+
+    let result = harness.send(accounts.record_price_ix(&accounts.admin.pubkey()), &accounts.admin);
+    assert_error_code(result, ErrorCode::InvalidPriceFeed)?;
 
     Ok(())
 }
 
+#[test]
+fn performance_package_vests_to_recipient_after_unlock() -> TestResult {
+    const VESTING_SECONDS: u32 = 1_000;
+
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let recipient = Keypair::new();
+    harness.airdrop(&recipient.pubkey(), 1_000_000_000)?;
+    let target_price = complete_with_package(&mut harness, &accounts, recipient.pubkey(), VESTING_SECONDS)?;
+
+    hold_price(&mut harness, &accounts, target_price)?;
+    harness.send(accounts.unlock_performance_package_ix(0), &accounts.admin)?;
+    let unlocked_at = harness.unix_timestamp();
+
+    let recipient_base = Pubkey::new_unique();
+    harness.create_mock_token_account(recipient_base, accounts.base_mint, recipient.pubkey())?;
+    let admin_base = Pubkey::new_unique();
+    harness.create_mock_token_account(admin_base, accounts.base_mint, accounts.admin.pubkey())?;
+
+    // only the recipient's account can receive the package
+    let result = harness.send(
+        accounts.claim_performance_package_ix(&accounts.admin.pubkey(), 0, admin_base),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::InvalidPackageRecipient)?;
+
+    harness.warp_to_timestamp(unlocked_at + VESTING_SECONDS as i64 / 4);
+    harness.send(
+        accounts.claim_performance_package_ix(&accounts.admin.pubkey(), 0, recipient_base),
+        &accounts.admin,
+    )?;
+    let package = harness.performance_package(&accounts.performance_package_pda(0))?;
+    assert_eq!(package.claimed_amount, COMPLETED_PACKAGE_ALLOCATION / 4);
+
+    harness.svm.expire_blockhash();
+    let result = harness.send(
+        accounts.claim_performance_package_ix(&accounts.admin.pubkey(), 0, recipient_base),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::NothingToClaim)?;
+
+    harness.warp_to_timestamp(unlocked_at + VESTING_SECONDS as i64);
+    harness.send(
+        accounts.claim_performance_package_ix(&recipient.pubkey(), 0, recipient_base),
+        &recipient,
+    )?;
+    let package = harness.performance_package(&accounts.performance_package_pda(0))?;
+    assert_eq!(package.claimed_amount, COMPLETED_PACKAGE_ALLOCATION);
+
+    harness.svm.expire_blockhash();
+    let result = harness.send(
+        accounts.claim_performance_package_ix(&recipient.pubkey(), 0, recipient_base),
+        &recipient,
+    );
+    assert_error_code(result, ErrorCode::PerformancePackageAlreadyClaimed)?;
+
+    Ok(())
+}
+
+#[test]
+fn configure_performance_packages_in_order_up_to_max() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+
+    let config = InitConfig {
+        performance_pool_base_tokens: 1_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    let recipient = accounts.admin.pubkey();
+
+    let result = harness.send(
+        accounts.configure_performance_package_ix(0, 20_000, 10, recipient, 0, true),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::InvalidPerformancePackageIndex)?;
+
+    let result = harness.send(
+        accounts.configure_performance_package_ix(1, 20_000, 10, recipient, 0, false),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::InvalidPerformancePackageIndex)?;
+
+    for index in 0..MAX_PERFORMANCE_PACKAGES as u8 {
+        harness.send(
+            accounts.configure_performance_package_ix(index, 20_000, 10, recipient, 0, index % 2 == 1),
+            &accounts.admin,
+        )?;
+    }
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.performance_package_count as usize, MAX_PERFORMANCE_PACKAGES);
+    assert_eq!(decoded.configured_performance_tokens, 10 * MAX_PERFORMANCE_PACKAGES as u64);
+
+    let result = harness.send(
+        accounts.configure_performance_package_ix(MAX_PERFORMANCE_PACKAGES as u8, 20_000, 10, recipient, 0, false),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::InvalidPerformancePackageIndex)?;
+
+    Ok(())
+}
+
+#[test]
+fn independent_package_unlocks_before_previous() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let recipient = accounts.admin.pubkey();
+
+    let packages = vec![
+        accounts.configure_performance_package_ix(0, 40_000, COMPLETED_PACKAGE_ALLOCATION, recipient, 0, false),
+        accounts.configure_performance_package_ix(1, 20_000, COMPLETED_PACKAGE_ALLOCATION, recipient, 0, true),
+        accounts.configure_performance_package_ix(2, 20_000, COMPLETED_PACKAGE_ALLOCATION, recipient, 0, false),
+    ];
+    let initial_price = complete_with_packages(&mut harness, &accounts, packages)?;
+    hold_price(&mut harness, &accounts, initial_price * 2)?;
+
+    let result = harness.send(accounts.unlock_performance_package_ix(1), &accounts.admin);
+    assert_error_code(result, ErrorCode::PreviousPackageNotUnlocked)?;
+
+    harness.send(accounts.unlock_performance_package_ix(2), &accounts.admin)?;
+    let package = harness.performance_package(&accounts.performance_package_pda(2))?;
+    assert!(package.is_unlocked);
+
+    let package = harness.performance_package(&accounts.performance_package_pda(0))?;
+    assert!(!package.is_unlocked);
+
+    Ok(())
+}
+
+#[test]
+fn fractional_multiplier_targets() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let recipient = accounts.admin.pubkey();
+
+    // 1.5x and 2.25x
+    let packages = vec![
+        accounts.configure_performance_package_ix(0, 15_000, COMPLETED_PACKAGE_ALLOCATION, recipient, 0, false),
+        accounts.configure_performance_package_ix(1, 22_500, COMPLETED_PACKAGE_ALLOCATION, recipient, 0, false),
+    ];
+    let initial_price = complete_with_packages(&mut harness, &accounts, packages)?;
+    let target_1_5x = initial_price * 3 / 2;
+    let target_2_25x = initial_price * 9 / 4;
+
+    hold_price(&mut harness, &accounts, target_1_5x - 1)?;
+    let result = harness.send(accounts.unlock_performance_package_ix(0), &accounts.admin);
+    assert_error_code(result, ErrorCode::PriceTargetNotReached)?;
+
+    hold_price(&mut harness, &accounts, target_1_5x)?;
+    harness.svm.expire_blockhash();
+    harness.send(accounts.unlock_performance_package_ix(0), &accounts.admin)?;
+    let result = harness.send(accounts.unlock_performance_package_ix(1), &accounts.admin);
+    assert_error_code(result, ErrorCode::PriceTargetNotReached)?;
+
+    hold_price(&mut harness, &accounts, target_2_25x)?;
+    harness.svm.expire_blockhash();
+    harness.send(accounts.unlock_performance_package_ix(1), &accounts.admin)?;
+
+    let package = harness.performance_package(&accounts.performance_package_pda(1))?;
+    assert!(package.is_unlocked);
+    assert_eq!(package.multiplier_bps, 22_500);
+
+    Ok(())
+}
+
+#[test]
+fn unconfigured_performance_tokens_are_burned_once() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    complete_with_package(&mut harness, &accounts, accounts.admin.pubkey(), 0)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    let remainder = decoded.calculated_performance_pool_tokens.unwrap() - decoded.configured_performance_tokens;
+    let vault_before = harness.token_balance(&accounts.base_vault)?;
+
+    harness.send(
+        accounts.release_unconfigured_performance_tokens_ix(PerformanceTokenRelease::Burn, None),
+        &accounts.admin,
+    )?;
+    assert_eq!(harness.token_balance(&accounts.base_vault)?, vault_before - remainder);
+
+    harness.svm.expire_blockhash();
+    let result = harness.send(
+        accounts.release_unconfigured_performance_tokens_ix(PerformanceTokenRelease::Burn, None),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::NothingToRelease)?;
+
+    Ok(())
+}
+
+#[test]
+fn unconfigured_performance_tokens_go_to_treasury() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    complete_with_package(&mut harness, &accounts, accounts.admin.pubkey(), 0)?;
+
+    let treasury_base = Pubkey::new_unique();
+    harness.create_mock_token_account(treasury_base, accounts.base_mint, accounts.treasury)?;
+
+    let result = harness.send(
+        accounts.release_unconfigured_performance_tokens_ix(PerformanceTokenRelease::Treasury, None),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::InvalidTreasuryAccountOwner)?;
+
+    harness.send(
+        accounts.release_unconfigured_performance_tokens_ix(PerformanceTokenRelease::Treasury, Some(treasury_base)),
+        &accounts.admin,
+    )?;
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert!(decoded.unconfigured_performance_released);
+    assert_eq!(
+        harness.token_balance(&treasury_base)?,
+        decoded.calculated_performance_pool_tokens.unwrap() - decoded.configured_performance_tokens
+    );
+
+    Ok(())
+}
+
+#[test]
+fn locked_package_expires_after_deadline() -> TestResult {
+    const EXPIRY_SECONDS: u32 = 30 * 24 * 60 * 60;
+
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let target_price = complete_with_package(&mut harness, &accounts, accounts.admin.pubkey(), 0)?;
+    harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+        ks.performance_package_expiry_seconds = EXPIRY_SECONDS;
+    })?;
+    let completed_at = harness.kickstarter_state(&accounts.kickstarter_pda)?.unix_timestamp_closed.unwrap();
+
+    let treasury_base = Pubkey::new_unique();
+    harness.create_mock_token_account(treasury_base, accounts.base_mint, accounts.treasury)?;
+
+    let result = harness.send(
+        accounts.expire_performance_package_ix(0, PerformanceTokenRelease::Treasury, Some(treasury_base)),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::PerformancePackageNotExpired)?;
+
+    harness.warp_to_timestamp(completed_at + EXPIRY_SECONDS as i64);
+    hold_price(&mut harness, &accounts, target_price)?;
+    let result = harness.send(accounts.unlock_performance_package_ix(0), &accounts.admin);
+    assert_error_code(result, ErrorCode::PerformancePackageExpired)?;
+
+    harness.svm.expire_blockhash();
+    harness.send(
+        accounts.expire_performance_package_ix(0, PerformanceTokenRelease::Treasury, Some(treasury_base)),
+        &accounts.admin,
+    )?;
+    assert_eq!(harness.token_balance(&treasury_base)?, COMPLETED_PACKAGE_ALLOCATION);
+    let package = harness.performance_package(&accounts.performance_package_pda(0))?;
+    assert!(package.is_expired);
+
+    harness.svm.expire_blockhash();
+    let result = harness.send(
+        accounts.expire_performance_package_ix(0, PerformanceTokenRelease::Burn, None),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::PerformancePackageExpired)?;
+
+    Ok(())
+}
@@ -1,10 +1,13 @@
 mod support;
 
-use kickstarter::state::KickstarterState;
+use anchor_lang::solana_program::program_pack::Pack;
+use kickstarter::{error::ErrorCode, merkle_tree::compute_proof, state::PrivateFundState};
 use solana_keypair::Keypair;
 use support::{
-    finalize_private_round_ix, fund_private_ix, InitConfig, KickstarterAccounts,
-    Signer, TestHarness, TestResult,
+    assert_error_code, commitment_leaf, deposit_private,
+    end_private_round_ix, finalize_private_round_ix, fund_private_ix, private_claim_ix,
+    private_compressed_claim_ix, start_private_round_ix, InitConfig,
+    KickstarterAccounts, Pubkey, Signer, TestHarness, TestResult,
 };
 
 #[test]
@@ -23,6 +26,7 @@ fn fund_private_updates_commitments_and_count() -> TestResult {
     })?;
 
     let salt = [1u8; 32]; // Simple salt for testing
+    deposit_private(&mut harness, &accounts, &user, 5_000_000)?;
     let ix_fund_private = fund_private_ix(
         &user.pubkey(),
         &accounts.kickstarter_pda,
@@ -59,6 +63,7 @@ fn finalize_private_round_verifies_commitments() -> TestResult {
     })?;
 
     let salt = [1u8; 32];
+    deposit_private(&mut harness, &accounts, &user, 5_000_000)?;
     let ix_fund_private = fund_private_ix(
         &user.pubkey(),
         &accounts.kickstarter_pda,
@@ -74,11 +79,304 @@ fn finalize_private_round_verifies_commitments() -> TestResult {
         &accounts.admin.pubkey(),
         &accounts.kickstarter_pda,
         &accounts.private_state,
+        &accounts.private_quote_vault,
+        &accounts.quote_vault,
         private_state.commitments_root,
         private_state.committed_amount,
-        [0u8; 64], 
+        &accounts.attester,
     );
-    harness.send(ix_finalize, &accounts.admin)?;
+    harness.send_all(ix_finalize, &accounts.admin)?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+fn setup_funded_private_round(harness: &mut TestHarness, accounts: &KickstarterAccounts) -> TestResult<PrivateFundState> {
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+        ks.hard_cap = u64::MAX;
+    })?;
+
+    deposit_private(harness, accounts, &user, 5_000_000)?;
+    harness.send(
+        fund_private_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, 5_000_000, [1u8; 32]),
+        &user,
+    )?;
+
+    harness.private_state(&accounts.private_state)
+}
+
+#[test]
+fn finalize_private_round_without_sigverify_fails() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let private_state = setup_funded_private_round(&mut harness, &accounts)?;
+
+    let mut ixs = finalize_private_round_ix(
+        &accounts.admin.pubkey(),
+        &accounts.kickstarter_pda,
+        &accounts.private_state,
+        &accounts.private_quote_vault,
+        &accounts.quote_vault,
+        private_state.commitments_root,
+        private_state.committed_amount,
+        &accounts.attester,
+    );
+    // drop the Ed25519 instruction, keep only finalize
+    ixs.remove(0);
+
+    let result = harness.send_all(ixs, &accounts.admin);
+    assert_error_code(result, ErrorCode::InvalidAttestation)?;
+
+    Ok(())
+}
+
+#[test]
+fn finalize_private_round_signed_by_wrong_attester_fails() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let private_state = setup_funded_private_round(&mut harness, &accounts)?;
+
+    let impostor = Keypair::new();
+    let result = harness.send_all(
+        finalize_private_round_ix(
+            &accounts.admin.pubkey(),
+            &accounts.kickstarter_pda,
+            &accounts.private_state,
+            &accounts.private_quote_vault,
+            &accounts.quote_vault,
+            private_state.commitments_root,
+            private_state.committed_amount,
+            &impostor,
+        ),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::InvalidAttestation)?;
+
+    let private_state = harness.private_state(&accounts.private_state)?;
+    assert_eq!(private_state.settled_amount, 0);
+
+    Ok(())
+}
+
+#[test]
+fn private_claim_with_unknown_salt_is_rejected() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    let amount = 5_000_000u64;
+    let salt = [1u8; 32];
+    deposit_private(&mut harness, &accounts, &user, amount)?;
+    harness.send(
+        fund_private_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, amount, salt),
+        &user,
+    )?;
+
+    harness.complete_private_raise(&accounts, amount)?;
+    let ks_state = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+
+    let user_base = Pubkey::new_unique();
+    harness.create_mock_token_account(user_base, accounts.base_mint, user.pubkey())?;
+
+    // Proof is valid for the real commitment, but the salt doesn't rebuild it.
+    let unknown_salt = [2u8; 32];
+    let ix_claim = private_claim_ix(
+        &user.pubkey(),
+        &accounts.kickstarter_pda,
+        &accounts.private_state,
+        &accounts.base_vault,
+        &user_base,
+        amount,
+        unknown_salt,
+        0,
+        compute_proof(&[commitment_leaf(&user.pubkey(), amount, salt)], 0),
+    );
+    let result = harness.send(ix_claim, &user);
+    assert_error_code(result, ErrorCode::InvalidCommitmentsRoot)?;
+
+    let user_base_acc = harness.account(&user_base).unwrap();
+    let claimed = anchor_spl::token::spl_token::state::Account::unpack(&user_base_acc.data)?.amount;
+    assert_eq!(claimed, 0);
+
+    // the same proof with the real salt goes through
+    let ix_claim = private_claim_ix(
+        &user.pubkey(),
+        &accounts.kickstarter_pda,
+        &accounts.private_state,
+        &accounts.base_vault,
+        &user_base,
+        amount,
+        salt,
+        0,
+        compute_proof(&[commitment_leaf(&user.pubkey(), amount, salt)], 0),
+    );
+    harness.send(ix_claim, &user)?;
+
+    let user_base_acc = harness.account(&user_base).unwrap();
+    let claimed = anchor_spl::token::spl_token::state::Account::unpack(&user_base_acc.data)?.amount;
+    assert_eq!(claimed, ks_state.total_base_tokens_for_investors);
+
+    Ok(())
+}
+
+#[test]
+fn fund_private_after_deadline_fails() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    deposit_private(&mut harness, &accounts, &user, 5_000_000)?;
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+
+    let result = harness.send(
+        fund_private_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, 5_000_000, [3u8; 32]),
+        &user,
+    );
+    assert_error_code(result, ErrorCode::FundingTimeIsOut)?;
+
+    let private_state = harness.private_state(&accounts.private_state)?;
+    assert_eq!(private_state.committed_amount, 0);
+
+    Ok(())
+}
+
+/// Runs a private round with a single commitment through completion, then
+/// shrinks the snapshot so the user's share no longer fits in u64.
+fn complete_with_unrepresentable_private_share(
+    harness: &mut TestHarness,
+    accounts: &KickstarterAccounts,
+    user: &Keypair,
+    amount: u64,
+    salt: [u8; 32],
+) -> TestResult {
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    deposit_private(harness, accounts, user, amount)?;
+    harness.send(
+        fund_private_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, amount, salt),
+        user,
+    )?;
+    harness.send(end_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    let private_state = harness.private_state(&accounts.private_state)?;
+    harness.send_all(finalize_private_round_ix(
+        &accounts.admin.pubkey(),
+        &accounts.kickstarter_pda,
+        &accounts.private_state,
+        &accounts.private_quote_vault,
+        &accounts.quote_vault,
+        private_state.commitments_root,
+        private_state.committed_amount,
+        &accounts.attester,
+    ), &accounts.admin)?;
+
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+    harness.send(accounts.complete_ix(amount), &accounts.admin)?;
+    harness.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin)?;
+    harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+        ks.total_committed_at_completion = Some(1);
+    })
+}
+
+#[test]
+fn private_claim_with_unrepresentable_share_fails_with_math_overflow() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    let (amount, salt) = (5_000_000u64, [5u8; 32]);
+    complete_with_unrepresentable_private_share(&mut harness, &accounts, &user, amount, salt)?;
+
+    let user_base = Pubkey::new_unique();
+    harness.create_mock_token_account(user_base, accounts.base_mint, user.pubkey())?;
+    let result = harness.send(
+        private_claim_ix(
+            &user.pubkey(),
+            &accounts.kickstarter_pda,
+            &accounts.private_state,
+            &accounts.base_vault,
+            &user_base,
+            amount,
+            salt,
+            0,
+            compute_proof(&[commitment_leaf(&user.pubkey(), amount, salt)], 0),
+        ),
+        &user,
+    );
+    assert_error_code(result, ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
+
+#[test]
+fn private_compressed_claim_with_unrepresentable_share_fails_with_math_overflow() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    let (amount, salt) = (5_000_000u64, [6u8; 32]);
+    complete_with_unrepresentable_private_share(&mut harness, &accounts, &user, amount, salt)?;
+
+    let compressed_token_account = Pubkey::new_unique();
+    harness.create_mock_token_account(compressed_token_account, accounts.base_mint, user.pubkey())?;
+    let result = harness.send(
+        private_compressed_claim_ix(
+            &user.pubkey(),
+            &accounts.kickstarter_pda,
+            &accounts.private_state,
+            &accounts.base_vault,
+            &compressed_token_account,
+            amount,
+            salt,
+            0,
+            compute_proof(&[commitment_leaf(&user.pubkey(), amount, salt)], 0),
+        ),
+        &user,
+    );
+    assert_error_code(result, ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
+
+#[test]
+fn fund_private_overflowing_total_fails_with_math_overflow() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    deposit_private(&mut harness, &accounts, &user, 1_000_000)?;
+    harness.update_account::<PrivateFundState, _>(&accounts.private_state, |state| {
+        state.committed_amount = u64::MAX;
+    })?;
+
+    let result = harness.send(
+        fund_private_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, 1_000_000, [7u8; 32]),
+        &user,
+    );
+    assert_error_code(result, ErrorCode::MathOverflow)?;
+
+    Ok(())
+}
@@ -1,13 +1,13 @@
 mod support;
 
 use anchor_lang::solana_program::program_pack::Pack;
-use kickstarter::state::KickstarterState;
+use kickstarter::{error::ErrorCode, merkle_tree::compute_proof, state::KickstarterState};
 use solana_keypair::Keypair;
-use sha2::{Digest, Sha256};
 use support::{
     private_claim_ix, fund_private_ix, private_refund_ix, private_compressed_claim_ix,
     start_private_round_ix, end_private_round_ix, finalize_private_round_ix,
-    InitConfig, KickstarterAccounts, Pubkey,
+    assert_error_code, commitment_leaf, deposit_private,
+    withdraw_private_deposit_ix, claim_ix, fund_ix, to_anchor_pubkey, InitConfig, KickstarterAccounts, Pubkey,
     Signer, TestHarness, TestResult,
 };
 
@@ -33,6 +33,7 @@ fn private_funding_to_private_claim_integration() -> TestResult {
     let salt = [42u8; 32];
     let amount = 5_000_000u64;
 
+    deposit_private(&mut harness, &accounts, &user, amount)?;
     let ix_fund_private = fund_private_ix(
         &user.pubkey(),
         &accounts.kickstarter_pda,
@@ -47,7 +48,21 @@ fn private_funding_to_private_claim_integration() -> TestResult {
     assert_eq!(private_state.investor_count, 1);
     assert_ne!(private_state.commitments_root, [0u8; 32]);
 
+    harness.send(end_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+    harness.send_all(finalize_private_round_ix(
+        &accounts.admin.pubkey(),
+        &accounts.kickstarter_pda,
+        &accounts.private_state,
+        &accounts.private_quote_vault,
+        &accounts.quote_vault,
+        private_state.commitments_root,
+        private_state.committed_amount,
+        &accounts.attester,
+    ), &accounts.admin)?;
+
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     harness.send(accounts.complete_ix(amount), &accounts.admin)?;
+    harness.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin)?;
 
     let ix_private_claim = private_claim_ix(
         &user.pubkey(),
@@ -57,6 +72,8 @@ fn private_funding_to_private_claim_integration() -> TestResult {
         &user_base,
         amount,
         salt,
+        0,
+        compute_proof(&[commitment_leaf(&user.pubkey(), amount, salt)], 0),
     );
     harness.send(ix_private_claim, &user)?;
 
@@ -90,6 +107,7 @@ fn private_compressed_claim_test() -> TestResult {
     let amount = 5_000_000u64;
     let salt = [42u8; 32];
 
+    deposit_private(&mut harness, &accounts, &user, amount)?;
     let ix_fund_private = fund_private_ix(
         &user.pubkey(),
         &accounts.kickstarter_pda,
@@ -102,16 +120,20 @@ fn private_compressed_claim_test() -> TestResult {
     harness.send(end_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
 
     let final_private_state = harness.private_state(&accounts.private_state)?;
-    harness.send(finalize_private_round_ix(
+    harness.send_all(finalize_private_round_ix(
         &accounts.admin.pubkey(),
         &accounts.kickstarter_pda,
         &accounts.private_state,
+        &accounts.private_quote_vault,
+        &accounts.quote_vault,
         final_private_state.commitments_root,
         final_private_state.committed_amount,
-        [0u8; 64],
+        &accounts.attester,
     ), &accounts.admin)?;
 
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     harness.send(accounts.complete_ix(final_private_state.committed_amount), &accounts.admin)?;
+    harness.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin)?;
 
     // Test compressed claim
     let compressed_token_account = Pubkey::new_unique();
@@ -127,6 +149,8 @@ fn private_compressed_claim_test() -> TestResult {
         &compressed_token_account,
         amount,
         salt,
+        0,
+        compute_proof(&[commitment_leaf(&user.pubkey(), amount, salt)], 0),
     );
 
     // This will fail in current setup since compression program is not integrated
@@ -155,11 +179,13 @@ fn private_full_flow_with_data_table() -> TestResult {
     harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
 
     let mut data_flow = Vec::new();
+    let mut leaves = Vec::new();
 
     let amounts = [1_000_000u64, 1_000_000u64, 1_000_000u64, 1_000_000u64];
     let salt = [42u8; 32]; // Same salt for all investors in this simplified test
 
     for (i, (investor, &amount)) in investors.iter().zip(&amounts).enumerate() {
+        deposit_private(&mut harness, &accounts, investor, amount)?;
         let ix_fund_private = fund_private_ix(
             &investor.pubkey(),
             &accounts.kickstarter_pda,
@@ -171,14 +197,11 @@ fn private_full_flow_with_data_table() -> TestResult {
 
         let private_state = harness.private_state(&accounts.private_state)?;
 
-        let mut hasher = Sha256::new();
-        hasher.update(investor.pubkey().as_ref());
-        hasher.update(&amount.to_le_bytes());
-        hasher.update(&salt);
-        let commitment_hash: [u8; 32] = hasher.finalize().into();
+        let commitment_hash = commitment_leaf(&investor.pubkey(), amount, salt);
+        leaves.push(commitment_hash);
 
         data_flow.push(format!(
-            "Investor {}: {} USDC -> Commitment: {:x?} (XOR root: {:x?})",
+            "Investor {}: {} USDC -> Commitment: {:x?} (root: {:x?})",
             i + 1,
             amount,
             commitment_hash,
@@ -189,16 +212,20 @@ fn private_full_flow_with_data_table() -> TestResult {
     harness.send(end_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
 
     let final_private_state = harness.private_state(&accounts.private_state)?;
-    harness.send(finalize_private_round_ix(
+    harness.send_all(finalize_private_round_ix(
         &accounts.admin.pubkey(),
         &accounts.kickstarter_pda,
         &accounts.private_state,
+        &accounts.private_quote_vault,
+        &accounts.quote_vault,
         final_private_state.commitments_root,
         final_private_state.committed_amount,
-        [0u8; 64],
+        &accounts.attester,
     ), &accounts.admin)?;
 
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
     harness.send(accounts.complete_ix(final_private_state.committed_amount), &accounts.admin)?;
+    harness.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin)?;
 
     for (i, (investor, &amount)) in investors.iter().zip(&amounts).enumerate() {
         let user_base = Pubkey::new_unique();
@@ -212,6 +239,8 @@ fn private_full_flow_with_data_table() -> TestResult {
             &user_base,
             amount,
             salt,
+            i as u32,
+            compute_proof(&leaves, i),
         );
         harness.send(ix_private_claim, investor)?;
 
@@ -254,6 +283,7 @@ fn private_refund_integration() -> TestResult {
     let amount = 5_000_000u64;
     let salt = [42u8; 32];
 
+    deposit_private(&mut harness, &accounts, &user, amount)?;
     let ix_fund_private = fund_private_ix(
         &user.pubkey(),
         &accounts.kickstarter_pda,
@@ -266,13 +296,15 @@ fn private_refund_integration() -> TestResult {
     harness.send(end_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
 
     let final_private_state = harness.private_state(&accounts.private_state)?;
-    harness.send(finalize_private_round_ix(
+    harness.send_all(finalize_private_round_ix(
         &accounts.admin.pubkey(),
         &accounts.kickstarter_pda,
         &accounts.private_state,
+        &accounts.private_quote_vault,
+        &accounts.quote_vault,
         final_private_state.commitments_root,
         final_private_state.committed_amount,
-        [0u8; 64],
+        &accounts.attester,
     ), &accounts.admin)?;
 
     harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
@@ -295,6 +327,8 @@ fn private_refund_integration() -> TestResult {
         &user_quote,
         amount,
         salt,
+        0,
+        compute_proof(&[commitment_leaf(&user.pubkey(), amount, salt)], 0),
     );
     harness.send(ix_private_refund, &user)?;
 
@@ -304,4 +338,578 @@ fn private_refund_integration() -> TestResult {
     assert_eq!(user_quote_balance_after, user_quote_balance_before + amount);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn private_refund_accepts_proof_for_earlier_investor() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+
+    let investors: Vec<Keypair> = (0..3).map(|_| Keypair::new()).collect();
+    for investor in &investors {
+        harness.airdrop(&investor.pubkey(), 10_000_000_000)?;
+    }
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    let amount = 1_000_000u64;
+    let mut leaves = Vec::new();
+    for (i, investor) in investors.iter().enumerate() {
+        let salt = [i as u8 + 1; 32];
+        deposit_private(&mut harness, &accounts, investor, amount)?;
+        harness.send(
+            fund_private_ix(&investor.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, amount, salt),
+            investor,
+        )?;
+        leaves.push(commitment_leaf(&investor.pubkey(), amount, salt));
+    }
+
+    harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+        ks.state = KickstarterState::Refunding;
+    })?;
+    harness.set_token_account_balance(accounts.quote_vault, accounts.quote_mint, accounts.kickstarter_pda, amount * 3)?;
+
+    let first = &investors[0];
+    let user_quote = Pubkey::new_unique();
+    harness.create_mock_token_account(user_quote, accounts.quote_mint, first.pubkey())?;
+
+    let ix_private_refund = private_refund_ix(
+        &first.pubkey(),
+        &accounts.kickstarter_pda,
+        &accounts.private_state,
+        &accounts.quote_vault,
+        &user_quote,
+        amount,
+        [1u8; 32],
+        0,
+        compute_proof(&leaves, 0),
+    );
+    harness.send(ix_private_refund, first)?;
+
+    let user_quote_acc = harness.account(&user_quote).unwrap();
+    let refunded = anchor_spl::token::spl_token::state::Account::unpack(&user_quote_acc.data)?.amount;
+    assert_eq!(refunded, amount);
+
+    Ok(())
+}
+
+#[test]
+fn private_claim_twice_fails_with_double_spend() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    let amount = 5_000_000u64;
+    let salt = [42u8; 32];
+    deposit_private(&mut harness, &accounts, &user, amount)?;
+    harness.send(
+        fund_private_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, amount, salt),
+        &user,
+    )?;
+
+    harness.complete_private_raise(&accounts, amount)?;
+    let ks_state = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+
+    let user_base = Pubkey::new_unique();
+    harness.create_mock_token_account(user_base, accounts.base_mint, user.pubkey())?;
+
+    let proof = compute_proof(&[commitment_leaf(&user.pubkey(), amount, salt)], 0);
+    let claim = |proof: Vec<[u8; 32]>| {
+        private_claim_ix(
+            &user.pubkey(),
+            &accounts.kickstarter_pda,
+            &accounts.private_state,
+            &accounts.base_vault,
+            &user_base,
+            amount,
+            salt,
+            0,
+            proof,
+        )
+    };
+    harness.send(claim(proof.clone()), &user)?;
+
+    harness.svm.expire_blockhash();
+    let result = harness.send(claim(proof), &user);
+    assert_error_code(result, ErrorCode::DoubleSpend)?;
+
+    let user_base_acc = harness.account(&user_base).unwrap();
+    let claimed = anchor_spl::token::spl_token::state::Account::unpack(&user_base_acc.data)?.amount;
+    assert_eq!(claimed, ks_state.total_base_tokens_for_investors);
+
+    Ok(())
+}
+
+#[test]
+fn private_claim_with_zero_entitlement_fails_with_nothing_to_claim() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    let amount = 1u64;
+    let salt = [43u8; 32];
+    deposit_private(&mut harness, &accounts, &user, amount)?;
+    harness.send(
+        fund_private_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, amount, salt),
+        &user,
+    )?;
+
+    // a share so small of the snapshot that it rounds down to no base tokens
+    harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+        ks.state = KickstarterState::Complete;
+        ks.total_committed_at_completion = Some(u64::MAX);
+        ks.treasury_vault = to_anchor_pubkey(&accounts.treasury_vault);
+    })?;
+
+    let user_base = Pubkey::new_unique();
+    harness.create_mock_token_account(user_base, accounts.base_mint, user.pubkey())?;
+    let result = harness.send(
+        private_claim_ix(
+            &user.pubkey(),
+            &accounts.kickstarter_pda,
+            &accounts.private_state,
+            &accounts.base_vault,
+            &user_base,
+            amount,
+            salt,
+            0,
+            compute_proof(&[commitment_leaf(&user.pubkey(), amount, salt)], 0),
+        ),
+        &user,
+    );
+    assert_error_code(result, ErrorCode::NothingToClaim)?;
+
+    Ok(())
+}
+
+#[test]
+fn private_claim_follows_vesting_schedule() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    let config = InitConfig {
+        vesting_tge_bps: 2_000,
+        vesting_cliff_seconds: 100,
+        vesting_duration_seconds: 1_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    let amount = 5_000_000u64;
+    let salt = [42u8; 32];
+    deposit_private(&mut harness, &accounts, &user, amount)?;
+    harness.send(
+        fund_private_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, amount, salt),
+        &user,
+    )?;
+
+    harness.complete_private_raise(&accounts, amount)?;
+    let ks_state = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    let entitlement = ks_state.total_base_tokens_for_investors;
+    let completed_at = ks_state.unix_timestamp_closed.ok_or("completion time missing")?;
+
+    let user_base = Pubkey::new_unique();
+    harness.create_mock_token_account(user_base, accounts.base_mint, user.pubkey())?;
+    let claim = || {
+        private_claim_ix(
+            &user.pubkey(),
+            &accounts.kickstarter_pda,
+            &accounts.private_state,
+            &accounts.base_vault,
+            &user_base,
+            amount,
+            salt,
+            0,
+            compute_proof(&[commitment_leaf(&user.pubkey(), amount, salt)], 0),
+        )
+    };
+
+    // only the TGE part right after completion
+    harness.send(claim(), &user)?;
+    assert_eq!(harness.token_balance(&user_base)?, entitlement / 5);
+
+    // nothing new before the cliff
+    harness.svm.expire_blockhash();
+    assert_error_code(harness.send(claim(), &user), ErrorCode::NothingToClaim)?;
+
+    harness.warp_to_timestamp(completed_at + 500);
+    harness.send(claim(), &user)?;
+    assert_eq!(harness.token_balance(&user_base)?, entitlement / 5 + entitlement * 4 / 5 / 2);
+
+    harness.warp_to_timestamp(completed_at + 1_000);
+    harness.send(claim(), &user)?;
+    assert_eq!(harness.token_balance(&user_base)?, entitlement);
+
+    harness.svm.expire_blockhash();
+    assert_error_code(harness.send(claim(), &user), ErrorCode::DoubleSpend)?;
+
+    Ok(())
+}
+
+#[test]
+fn private_refund_twice_fails_with_double_spend() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    let amount = 5_000_000u64;
+    let salt = [42u8; 32];
+    deposit_private(&mut harness, &accounts, &user, amount)?;
+    harness.send(
+        fund_private_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, amount, salt),
+        &user,
+    )?;
+
+    harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+        ks.state = KickstarterState::Refunding;
+    })?;
+    harness.set_token_account_balance(accounts.quote_vault, accounts.quote_mint, accounts.kickstarter_pda, amount * 2)?;
+
+    let user_quote = Pubkey::new_unique();
+    harness.create_mock_token_account(user_quote, accounts.quote_mint, user.pubkey())?;
+
+    let proof = compute_proof(&[commitment_leaf(&user.pubkey(), amount, salt)], 0);
+    let refund = |proof: Vec<[u8; 32]>| {
+        private_refund_ix(
+            &user.pubkey(),
+            &accounts.kickstarter_pda,
+            &accounts.private_state,
+            &accounts.quote_vault,
+            &user_quote,
+            amount,
+            salt,
+            0,
+            proof,
+        )
+    };
+    harness.send(refund(proof.clone()), &user)?;
+
+    harness.svm.expire_blockhash();
+    let result = harness.send(refund(proof), &user);
+    assert_error_code(result, ErrorCode::DoubleSpend)?;
+
+    let user_quote_acc = harness.account(&user_quote).unwrap();
+    let refunded = anchor_spl::token::spl_token::state::Account::unpack(&user_quote_acc.data)?.amount;
+    assert_eq!(refunded, amount);
+
+    Ok(())
+}
+
+#[test]
+fn private_commitments_are_escrowed_and_settled_into_quote_vault() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    let deposit = 8_000_000u64;
+    let amount = 5_000_000u64;
+    let user_quote = deposit_private(&mut harness, &accounts, &user, deposit)?;
+
+    let over_deposit = fund_private_ix(
+        &user.pubkey(),
+        &accounts.kickstarter_pda,
+        &accounts.private_state,
+        deposit + 1,
+        [7u8; 32],
+    );
+    assert_error_code(harness.send(over_deposit, &user), ErrorCode::InsufficientPrivateDeposit)?;
+
+    harness.send(
+        fund_private_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, amount, [7u8; 32]),
+        &user,
+    )?;
+
+    let private_state = harness.private_state(&accounts.private_state)?;
+    harness.send_all(finalize_private_round_ix(
+        &accounts.admin.pubkey(),
+        &accounts.kickstarter_pda,
+        &accounts.private_state,
+        &accounts.private_quote_vault,
+        &accounts.quote_vault,
+        private_state.commitments_root,
+        private_state.committed_amount,
+        &accounts.attester,
+    ), &accounts.admin)?;
+
+    let balance = |harness: &TestHarness, key: &Pubkey| -> TestResult<u64> {
+        let account = harness.account(key).ok_or("token account not found")?;
+        Ok(anchor_spl::token::spl_token::state::Account::unpack(&account.data)?.amount)
+    };
+    assert_eq!(balance(&harness, &accounts.quote_vault)?, amount);
+    assert_eq!(balance(&harness, &accounts.private_quote_vault)?, deposit - amount);
+    assert_eq!(harness.private_state(&accounts.private_state)?.settled_amount, amount);
+
+    harness.send(
+        withdraw_private_deposit_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.private_quote_vault),
+        &user,
+    )?;
+    assert_eq!(balance(&harness, &user_quote)?, deposit - amount);
+    assert_eq!(balance(&harness, &accounts.private_quote_vault)?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn public_and_private_commitments_share_one_allocation() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let public_user = Keypair::new();
+    let private_user = Keypair::new();
+    harness.airdrop(&public_user.pubkey(), 10_000_000_000)?;
+    harness.airdrop(&private_user.pubkey(), 10_000_000_000)?;
+
+    // neither side reaches the minimum on its own
+    let config = InitConfig {
+        minimum_raise_amount: 4_000_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    harness.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+        ks.hard_cap = u64::MAX;
+    })?;
+
+    let public_amount = 3_000_000u64;
+    let private_amount = 1_000_000u64;
+    let salt = [9u8; 32];
+
+    let public_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(public_quote, accounts.quote_mint, public_user.pubkey(), public_amount)?;
+    harness.send(
+        fund_ix(&public_user.pubkey(), &accounts.kickstarter_pda, &public_quote, &accounts.quote_vault, public_amount),
+        &public_user,
+    )?;
+
+    deposit_private(&mut harness, &accounts, &private_user, private_amount)?;
+    harness.send(
+        fund_private_ix(&private_user.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, private_amount, salt),
+        &private_user,
+    )?;
+
+    harness.send(end_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+    let private_state = harness.private_state(&accounts.private_state)?;
+    harness.send_all(finalize_private_round_ix(
+        &accounts.admin.pubkey(),
+        &accounts.kickstarter_pda,
+        &accounts.private_state,
+        &accounts.private_quote_vault,
+        &accounts.quote_vault,
+        private_state.commitments_root,
+        private_state.committed_amount,
+        &accounts.attester,
+    ), &accounts.admin)?;
+
+    let total = public_amount + private_amount;
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+    harness.send(accounts.complete_ix(total), &accounts.admin)?;
+    harness.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin)?;
+
+    let ks_state = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(ks_state.state, KickstarterState::Complete);
+    assert_eq!(ks_state.total_committed_at_completion, Some(total));
+
+    let public_base = Pubkey::new_unique();
+    harness.create_mock_token_account(public_base, accounts.base_mint, public_user.pubkey())?;
+    harness.send(
+        claim_ix(&public_user.pubkey(), &accounts.kickstarter_pda, &accounts.base_vault, &public_base),
+        &public_user,
+    )?;
+
+    let private_base = Pubkey::new_unique();
+    harness.create_mock_token_account(private_base, accounts.base_mint, private_user.pubkey())?;
+    harness.send(
+        private_claim_ix(
+            &private_user.pubkey(),
+            &accounts.kickstarter_pda,
+            &accounts.private_state,
+            &accounts.base_vault,
+            &private_base,
+            private_amount,
+            salt,
+            0,
+            compute_proof(&[commitment_leaf(&private_user.pubkey(), private_amount, salt)], 0),
+        ),
+        &private_user,
+    )?;
+
+    let balance = |harness: &TestHarness, key: &Pubkey| -> TestResult<u64> {
+        let account = harness.account(key).ok_or("token account not found")?;
+        Ok(anchor_spl::token::spl_token::state::Account::unpack(&account.data)?.amount)
+    };
+    let investors_pool = ks_state.total_base_tokens_for_investors;
+    assert_eq!(balance(&harness, &public_base)?, investors_pool / 4 * 3);
+    assert_eq!(balance(&harness, &private_base)?, investors_pool / 4);
+
+    Ok(())
+}
+
+#[test]
+fn private_oversubscription_refund_after_complete() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+
+    harness.send(accounts.initialize_ix(InitConfig::default()), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    let amount = 4_000_000u64;
+    let final_raise = 3_000_000u64;
+    let salt = [21u8; 32];
+
+    deposit_private(&mut harness, &accounts, &user, amount)?;
+    harness.send(
+        fund_private_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, amount, salt),
+        &user,
+    )?;
+
+    harness.send(end_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+    let private_state = harness.private_state(&accounts.private_state)?;
+    harness.send_all(finalize_private_round_ix(
+        &accounts.admin.pubkey(),
+        &accounts.kickstarter_pda,
+        &accounts.private_state,
+        &accounts.private_quote_vault,
+        &accounts.quote_vault,
+        private_state.commitments_root,
+        private_state.committed_amount,
+        &accounts.attester,
+    ), &accounts.admin)?;
+
+    // only 3 of the 4 committed are accepted
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+    harness.send(accounts.complete_ix(final_raise), &accounts.admin)?;
+    harness.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin)?;
+    assert_eq!(harness.kickstarter_state(&accounts.kickstarter_pda)?.state, KickstarterState::Complete);
+
+    let user_quote = Pubkey::new_unique();
+    harness.create_mock_token_account(user_quote, accounts.quote_mint, user.pubkey())?;
+    let proof = compute_proof(&[commitment_leaf(&user.pubkey(), amount, salt)], 0);
+    let refund = |proof: Vec<[u8; 32]>| private_refund_ix(
+        &user.pubkey(),
+        &accounts.kickstarter_pda,
+        &accounts.private_state,
+        &accounts.quote_vault,
+        &user_quote,
+        amount,
+        salt,
+        0,
+        proof,
+    );
+
+    harness.send(refund(proof.clone()), &user)?;
+    let user_quote_acc = harness.account(&user_quote).ok_or("token account not found")?;
+    let refunded = anchor_spl::token::spl_token::state::Account::unpack(&user_quote_acc.data)?.amount;
+    assert_eq!(refunded, amount - final_raise);
+
+    harness.svm.expire_blockhash();
+    let result = harness.send(refund(proof.clone()), &user);
+    assert_error_code(result, ErrorCode::DoubleSpend)?;
+
+    // the accepted part is still claimable through the same nullifier
+    let user_base = Pubkey::new_unique();
+    harness.create_mock_token_account(user_base, accounts.base_mint, user.pubkey())?;
+    harness.send(
+        private_claim_ix(
+            &user.pubkey(),
+            &accounts.kickstarter_pda,
+            &accounts.private_state,
+            &accounts.base_vault,
+            &user_base,
+            amount,
+            salt,
+            0,
+            proof,
+        ),
+        &user,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn trigger_refunds_settles_unfinalized_private_escrow() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let user = Keypair::new();
+    let stranger = Keypair::new();
+    harness.airdrop(&user.pubkey(), 10_000_000_000)?;
+    harness.airdrop(&stranger.pubkey(), 10_000_000_000)?;
+
+    let config = InitConfig {
+        minimum_raise_amount: 10_000_000,
+        ..Default::default()
+    };
+    harness.send(accounts.initialize_ix(config), &accounts.admin)?;
+    harness.send(accounts.start_ix(), &accounts.admin)?;
+    harness.send(start_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+
+    let amount = 2_000_000u64;
+    let salt = [17u8; 32];
+    deposit_private(&mut harness, &accounts, &user, amount)?;
+    harness.send(
+        fund_private_ix(&user.pubkey(), &accounts.kickstarter_pda, &accounts.private_state, amount, salt),
+        &user,
+    )?;
+
+    // the admin never finalizes the private round
+    harness.warp_to_deadline(&accounts.kickstarter_pda)?;
+    harness.send(accounts.trigger_refunds_ix(&stranger.pubkey()), &stranger)?;
+
+    let private_state = harness.private_state(&accounts.private_state)?;
+    assert_eq!(private_state.settled_amount, amount);
+    assert_eq!(harness.token_balance(&accounts.private_quote_vault)?, 0);
+    assert_eq!(harness.token_balance(&accounts.quote_vault)?, amount);
+
+    let user_quote = Pubkey::new_unique();
+    harness.create_mock_token_account(user_quote, accounts.quote_mint, user.pubkey())?;
+    harness.send(
+        private_refund_ix(
+            &user.pubkey(),
+            &accounts.kickstarter_pda,
+            &accounts.private_state,
+            &accounts.quote_vault,
+            &user_quote,
+            amount,
+            salt,
+            0,
+            compute_proof(&[commitment_leaf(&user.pubkey(), amount, salt)], 0),
+        ),
+        &user,
+    )?;
+
+    let user_quote_acc = harness.account(&user_quote).ok_or("token account not found")?;
+    let refunded = anchor_spl::token::spl_token::state::Account::unpack(&user_quote_acc.data)?.amount;
+    assert_eq!(refunded, amount);
+
+    Ok(())
+}
@@ -1,5 +1,4 @@
 use anchor_lang::{AnchorDeserialize, InstructionData, ToAccountMetas};
-use borsh::BorshDeserialize;
 use light_client::indexer::TreeInfo;
 use light_program_test::{
     program_test::LightProgramTest, AddressWithTree, Indexer, ProgramTestConfig, Rpc, RpcError,
@@ -11,11 +11,12 @@ use anchor_spl::token;
 use anchor_spl::token::spl_token::state::{
     Account as SplTokenAccount, AccountState, Mint as SplMint,
 };
-use kickstarter::{constants, state::{Kickstarter, PrivateFundState}};
-use ephemeral_rollups_sdk::consts::{MAGIC_CONTEXT_ID, MAGIC_PROGRAM_ID};
+use kickstarter::{constants, error::ErrorCode, merkle_tree, oracle, state::{FunderPosition, Kickstarter, PerformancePackage, PerformanceTokenRelease, PrivateFundState}};
+use ephemeral_rollups_sdk::consts::{DELEGATION_PROGRAM_ID, MAGIC_CONTEXT_ID, MAGIC_PROGRAM_ID};
 use litesvm::LiteSVM;
 use mpl_token_metadata::ID as MPL_TOKEN_METADATA_ID;
 use solana_account::Account;
+use solana_clock::Clock;
 use solana_instruction::{account_meta::AccountMeta, Instruction};
 use solana_keypair::Keypair;
 use solana_message::Message;
@@ -25,6 +26,7 @@ use solana_transaction::Transaction;
 
 pub type TestResult<T = ()> = Result<T, Box<dyn Error>>;
 
+pub const TEST_PRICE_FEED_ID: [u8; 32] = [7u8; 32];
 pub const TEST_BASE_VAULT_BALANCE: u64 = 1_000_000_000_000_000_000;
 
 pub fn program_artifact_path() -> &'static str {
@@ -76,6 +78,19 @@ pub fn pack_token_account(account: SplTokenAccount) -> Vec<u8> {
     data
 }
 
+/// Asserts that `result` failed with the given program error code.
+pub fn assert_error_code(result: TestResult, code: ErrorCode) -> TestResult {
+    let err = match result {
+        Ok(()) => return Err(format!("expected {code:?} but the transaction succeeded").into()),
+        Err(err) => err.to_string(),
+    };
+    let expected = format!("Custom({})", u32::from(code));
+    if !err.contains(&expected) {
+        return Err(format!("expected {expected}, got: {err}").into());
+    }
+    Ok(())
+}
+
 pub struct TestHarness {
     pub svm: LiteSVM,
     pub program_id: Pubkey,
@@ -118,8 +133,12 @@ impl TestHarness {
     }
 
     pub fn send(&mut self, ix: Instruction, signer: &Keypair) -> TestResult {
+        self.send_all(vec![ix], signer)
+    }
+
+    pub fn send_all(&mut self, ixs: Vec<Instruction>, signer: &Keypair) -> TestResult {
         let blockhash = self.svm.latest_blockhash();
-        let message = Message::new(&[ix], Some(&signer.pubkey()));
+        let message = Message::new(&ixs, Some(&signer.pubkey()));
         let mut tx = Transaction::new_unsigned(message);
         tx.sign(&[signer], blockhash);
         self.svm
@@ -144,6 +163,23 @@ impl TestHarness {
         Ok(PrivateFundState::try_deserialize(&mut data_slice)?)
     }
 
+    pub fn token_balance(&self, key: &Pubkey) -> TestResult<u64> {
+        let account = self.account(key).ok_or("token account not found")?;
+        Ok(SplTokenAccount::unpack(&account.data)?.amount)
+    }
+
+    pub fn performance_package(&self, pda: &Pubkey) -> TestResult<PerformancePackage> {
+        let account = self.account(pda).ok_or("performance package account not found")?;
+        let mut data_slice = account.data.as_slice();
+        Ok(PerformancePackage::try_deserialize(&mut data_slice)?)
+    }
+
+    pub fn funder_position(&self, pda: &Pubkey) -> TestResult<FunderPosition> {
+        let account = self.account(pda).ok_or("funder position account not found")?;
+        let mut data_slice = account.data.as_slice();
+        Ok(FunderPosition::try_deserialize(&mut data_slice)?)
+    }
+
     pub fn create_mock_mint(&mut self, mint: Pubkey, mint_authority: Pubkey) -> TestResult {
         let rent = self.svm.minimum_balance_for_rent_exemption(<SplMint as Pack>::LEN);
         let mint_data = pack_mint_account(SplMint {
@@ -235,6 +271,157 @@ impl TestHarness {
         Ok(())
     }
 
+    pub fn unix_timestamp(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    pub fn warp_to_timestamp(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    /// Moves the clock to the funding deadline set by `start_kickstarter`.
+    pub fn warp_to_deadline(&mut self, kickstarter_pda: &Pubkey) -> TestResult {
+        let deadline = self
+            .kickstarter_state(kickstarter_pda)?
+            .unix_timestamp_closed
+            .ok_or("kickstarter has no deadline")?;
+        self.warp_to_timestamp(deadline);
+        Ok(())
+    }
+
+    /// Runs a raise to a successful completion: initializes with `config`, sends
+    /// `setup` (e.g. package configuration) before start, fills the raise with
+    /// `final_raise_amount`, completes it and seeds the pool. Returns the
+    /// completion timestamp.
+    pub fn complete_raise(
+        &mut self,
+        accounts: &KickstarterAccounts,
+        config: InitConfig,
+        setup: Vec<Instruction>,
+        final_raise_amount: u64,
+    ) -> TestResult<i64> {
+        self.send(accounts.initialize_ix(config), &accounts.admin)?;
+        for ix in setup {
+            self.send(ix, &accounts.admin)?;
+        }
+        self.send(accounts.start_ix(), &accounts.admin)?;
+
+        self.update_kickstarter(&accounts.kickstarter_pda, |ks| {
+            ks.total_committed_amount = final_raise_amount;
+        })?;
+        self.set_token_account_balance(
+            accounts.quote_vault,
+            accounts.quote_mint,
+            accounts.kickstarter_pda,
+            final_raise_amount,
+        )?;
+
+        self.warp_to_deadline(&accounts.kickstarter_pda)?;
+        self.send(accounts.complete_ix(final_raise_amount), &accounts.admin)?;
+        self.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin)?;
+        Ok(self.unix_timestamp())
+    }
+
+    /// Ends and settles a funded private round, then completes the raise and seeds the pool.
+    pub fn complete_private_raise(
+        &mut self,
+        accounts: &KickstarterAccounts,
+        final_raise_amount: u64,
+    ) -> TestResult<i64> {
+        self.send(end_private_round_ix(&accounts.admin.pubkey(), &accounts.kickstarter_pda), &accounts.admin)?;
+        let private_state = self.private_state(&accounts.private_state)?;
+        self.send_all(
+            finalize_private_round_ix(
+                &accounts.admin.pubkey(),
+                &accounts.kickstarter_pda,
+                &accounts.private_state,
+                &accounts.private_quote_vault,
+                &accounts.quote_vault,
+                private_state.commitments_root,
+                private_state.committed_amount,
+                &accounts.attester,
+            ),
+            &accounts.admin,
+        )?;
+
+        self.warp_to_deadline(&accounts.kickstarter_pda)?;
+        self.send(accounts.complete_ix(final_raise_amount), &accounts.admin)?;
+        self.send(accounts.seed_pool_ix(&accounts.admin.pubkey()), &accounts.admin)?;
+        Ok(self.unix_timestamp())
+    }
+
+    /// Rewrites any fixed-size program account in place, for driving state to edge cases.
+    pub fn update_account<T, F>(&mut self, pda: &Pubkey, updater: F) -> TestResult
+    where
+        T: AccountSerialize + AccountDeserialize,
+        F: FnOnce(&mut T),
+    {
+        let mut account = self.account(pda).ok_or("account not found")?;
+        let mut data_slice: &[u8] = &account.data;
+        let mut state = T::try_deserialize(&mut data_slice)?;
+
+        updater(&mut state);
+
+        let mut cursor = Cursor::new(&mut account.data[..]);
+        state.try_serialize(&mut cursor)?;
+        self.svm
+            .set_account(*pda, account)
+            .map_err(|e| format!("{:?}", e))?;
+        Ok(())
+    }
+
+    /// Hands a program account to the delegation program, as delegating it to the
+    /// ephemeral rollup does on the base layer.
+    pub fn delegate_account(&mut self, pda: &Pubkey) -> TestResult {
+        let mut account = self.account(pda).ok_or("account not found")?;
+        account.owner = Pubkey::new_from_array(DELEGATION_PROGRAM_ID.to_bytes());
+        self.svm
+            .set_account(*pda, account)
+            .map_err(|e| format!("{:?}", e))?;
+        Ok(())
+    }
+
+    /// Writes a fully verified Pyth `PriceUpdateV2` account with the given price.
+    pub fn set_price_feed(
+        &mut self,
+        price_feed: Pubkey,
+        price: i64,
+        conf: u64,
+        exponent: i32,
+        publish_time: i64,
+    ) -> TestResult {
+        let mut data = Vec::with_capacity(134);
+        data.extend_from_slice(&oracle::PRICE_UPDATE_V2_DISCRIMINATOR);
+        data.extend_from_slice(&[0u8; 32]); // write_authority
+        data.push(1); // VerificationLevel::Full
+        data.extend_from_slice(&TEST_PRICE_FEED_ID);
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&conf.to_le_bytes());
+        data.extend_from_slice(&exponent.to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes()); // prev_publish_time
+        data.extend_from_slice(&price.to_le_bytes()); // ema_price
+        data.extend_from_slice(&conf.to_le_bytes()); // ema_conf
+        data.extend_from_slice(&0u64.to_le_bytes()); // posted_slot
+
+        let rent = self.svm.minimum_balance_for_rent_exemption(data.len());
+        self.svm
+            .set_account(
+                price_feed,
+                Account {
+                    lamports: rent,
+                    data,
+                    owner: to_solana_pubkey(&oracle::PYTH_RECEIVER_PROGRAM_ID),
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            )
+            .map_err(|err| format!("failed to set price feed: {err:?}"))?;
+        Ok(())
+    }
+
     pub fn update_kickstarter<F>(&mut self, pda: &Pubkey, updater: F) -> TestResult
     where
         F: FnOnce(&mut Kickstarter),
@@ -260,16 +447,24 @@ impl TestHarness {
 
 pub struct KickstarterAccounts {
     pub admin: Keypair,
+    pub attester: Keypair,
+    pub price_feed: Pubkey,
+    pub price_observations: Pubkey,
     pub kickstarter_pda: Pubkey,
     pub base_mint: Pubkey,
     pub quote_mint: Pubkey,
     pub base_vault: Pubkey,
     pub quote_vault: Pubkey,
+    pub private_quote_vault: Pubkey,
     pub private_state: Pubkey,
     pub treasury: Pubkey,
     pub treasury_token_account: Pubkey,
-    pub liquidity_token_account: Pubkey,
-    pub liquidity_base_token_account: Pubkey,
+    pub liquidity_pool: Pubkey,
+    pub pool_base_vault: Pubkey,
+    pub pool_quote_vault: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_vault: Pubkey,
+    pub treasury_vault: Pubkey,
     pub metadata_pda: Pubkey,
 }
 
@@ -300,10 +495,18 @@ impl KickstarterAccounts {
             &[constants::SEED_QUOTE_VAULT.as_bytes(), kickstarter_pda.as_ref()],
             &program_id,
         );
+        let (private_quote_vault, _) = Pubkey::find_program_address(
+            &[constants::SEED_PRIVATE_QUOTE_VAULT.as_bytes(), kickstarter_pda.as_ref()],
+            &program_id,
+        );
         let (private_state, _) = Pubkey::find_program_address(
             &[constants::SEED_PRIVATE_STATE.as_bytes(), kickstarter_pda.as_ref()],
             &program_id,
         );
+        let (price_observations, _) = Pubkey::find_program_address(
+            &[constants::SEED_PRICE_OBSERVATIONS.as_bytes(), kickstarter_pda.as_ref()],
+            &program_id,
+        );
 
         // In CLI, treasury = admin, so we use admin.pubkey() as treasury
         let treasury = admin.pubkey();
@@ -315,24 +518,36 @@ impl KickstarterAccounts {
         let treasury_token_account = Pubkey::new_unique();
         harness.create_mock_token_account(treasury_token_account, quote_mint, treasury)?;
 
-        let liquidity_token_account = Pubkey::new_unique();
-        harness.create_mock_token_account(liquidity_token_account, quote_mint, admin.pubkey())?;
-
-        let liquidity_base_token_account = Pubkey::new_unique();
-        harness.create_mock_token_account(liquidity_base_token_account, base_mint, admin.pubkey())?;
+        let pool_pda = |seed: &str| {
+            Pubkey::find_program_address(&[seed.as_bytes(), kickstarter_pda.as_ref()], &program_id).0
+        };
+        let liquidity_pool = pool_pda(constants::SEED_LIQUIDITY_POOL);
+        let pool_base_vault = pool_pda(constants::SEED_POOL_BASE_VAULT);
+        let pool_quote_vault = pool_pda(constants::SEED_POOL_QUOTE_VAULT);
+        let lp_mint = pool_pda(constants::SEED_LP_MINT);
+        let lp_vault = pool_pda(constants::SEED_LP_VAULT);
+        let treasury_vault = pool_pda(constants::SEED_TREASURY_VAULT);
 
         Ok(Self {
             admin,
+            attester: Keypair::new(),
+            price_feed: Pubkey::new_unique(),
+            price_observations,
             kickstarter_pda,
             base_mint,
             quote_mint,
             base_vault,
             quote_vault,
+            private_quote_vault,
             private_state,
             treasury,
             treasury_token_account,
-            liquidity_token_account,
-            liquidity_base_token_account,
+            liquidity_pool,
+            pool_base_vault,
+            pool_quote_vault,
+            lp_mint,
+            lp_vault,
+            treasury_vault,
             metadata_pda,
         })
     }
@@ -353,6 +568,7 @@ impl KickstarterAccounts {
             quote_mint: to_anchor_pubkey(&self.quote_mint),
             base_vault: to_anchor_pubkey(&self.base_vault),
             quote_vault: to_anchor_pubkey(&self.quote_vault),
+            private_quote_vault: to_anchor_pubkey(&self.private_quote_vault),
             private_state: to_anchor_pubkey(&self.private_state),
             treasury: to_anchor_pubkey(&self.treasury),
             token_program: anchor_spl::token::ID,
@@ -377,6 +593,22 @@ impl KickstarterAccounts {
                 token_symbol: config.token_symbol,
                 token_description: config.token_description,
                 token_image_url: config.token_image_url,
+                private_round_attester: to_anchor_pubkey(&self.attester.pubkey()),
+                hard_cap: config.hard_cap,
+                close_on_hard_cap: config.close_on_hard_cap,
+                withdrawal_cutoff_seconds: config.withdrawal_cutoff_seconds,
+                vesting_tge_bps: config.vesting_tge_bps,
+                vesting_cliff_seconds: config.vesting_cliff_seconds,
+                vesting_duration_seconds: config.vesting_duration_seconds,
+                price_feed: to_anchor_pubkey(&self.price_feed),
+                price_feed_id: TEST_PRICE_FEED_ID,
+                max_price_age_seconds: config.max_price_age_seconds,
+                max_price_confidence_bps: config.max_price_confidence_bps,
+                twap_window_seconds: config.twap_window_seconds,
+                performance_package_expiry_seconds: config.performance_package_expiry_seconds,
+                liquidity_lock_seconds: config.liquidity_lock_seconds,
+                liquidity_bps: config.liquidity_bps,
+                base_tokens_for_liquidity_bps: config.base_tokens_for_liquidity_bps,
             }
             .data(),
         }
@@ -398,12 +630,10 @@ impl KickstarterAccounts {
         let accounts = kickstarter::accounts::CompleteKickstarter {
             admin: to_anchor_pubkey(&self.admin.pubkey()),
             kickstarter: to_anchor_pubkey(&self.kickstarter_pda),
-            quote_vault: to_anchor_pubkey(&self.quote_vault),
-            treasury_token_account: to_anchor_pubkey(&self.treasury_token_account),
-            liquidity_token_account: to_anchor_pubkey(&self.liquidity_token_account),
+            private_state: to_anchor_pubkey(&self.private_state),
             base_vault: to_anchor_pubkey(&self.base_vault),
-            liquidity_base_token_account: to_anchor_pubkey(&self.liquidity_base_token_account),
             base_mint: to_anchor_pubkey(&self.base_mint),
+            quote_mint: to_anchor_pubkey(&self.quote_mint),
             token_program: anchor_spl::token::ID,
         };
         Instruction {
@@ -413,39 +643,202 @@ impl KickstarterAccounts {
         }
     }
 
-    pub fn configure_performance_package_ix(&self, index: u8, multiplier: u8, allocation: u64) -> Instruction {
+    pub fn seed_pool_ix(&self, payer: &Pubkey) -> Instruction {
+        let accounts = kickstarter::accounts::SeedPool {
+            payer: to_anchor_pubkey(payer),
+            kickstarter: to_anchor_pubkey(&self.kickstarter_pda),
+            quote_vault: to_anchor_pubkey(&self.quote_vault),
+            base_vault: to_anchor_pubkey(&self.base_vault),
+            base_mint: to_anchor_pubkey(&self.base_mint),
+            quote_mint: to_anchor_pubkey(&self.quote_mint),
+            liquidity_pool: to_anchor_pubkey(&self.liquidity_pool),
+            pool_base_vault: to_anchor_pubkey(&self.pool_base_vault),
+            pool_quote_vault: to_anchor_pubkey(&self.pool_quote_vault),
+            lp_mint: to_anchor_pubkey(&self.lp_mint),
+            lp_vault: to_anchor_pubkey(&self.lp_vault),
+            treasury_vault: to_anchor_pubkey(&self.treasury_vault),
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        };
+        Instruction {
+            program_id: program_id(),
+            accounts: convert_metas(accounts.to_account_metas(Some(true))),
+            data: kickstarter::instruction::SeedPool {}.data(),
+        }
+    }
+
+    pub fn swap_ix(
+        &self,
+        user: &Pubkey,
+        user_base_account: Pubkey,
+        user_quote_account: Pubkey,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        base_to_quote: bool,
+    ) -> Instruction {
+        let accounts = kickstarter::accounts::Swap {
+            user: to_anchor_pubkey(user),
+            kickstarter: to_anchor_pubkey(&self.kickstarter_pda),
+            liquidity_pool: to_anchor_pubkey(&self.liquidity_pool),
+            pool_base_vault: to_anchor_pubkey(&self.pool_base_vault),
+            pool_quote_vault: to_anchor_pubkey(&self.pool_quote_vault),
+            user_base_account: to_anchor_pubkey(&user_base_account),
+            user_quote_account: to_anchor_pubkey(&user_quote_account),
+            token_program: anchor_spl::token::ID,
+        };
+        Instruction {
+            program_id: program_id(),
+            accounts: convert_metas(accounts.to_account_metas(Some(true))),
+            data: kickstarter::instruction::Swap {
+                amount_in,
+                minimum_amount_out,
+                base_to_quote,
+            }
+            .data(),
+        }
+    }
+
+    pub fn withdraw_locked_liquidity_ix(
+        &self,
+        admin: &Pubkey,
+        admin_base_account: Pubkey,
+        admin_quote_account: Pubkey,
+    ) -> Instruction {
+        let accounts = kickstarter::accounts::WithdrawLockedLiquidity {
+            admin: to_anchor_pubkey(admin),
+            kickstarter: to_anchor_pubkey(&self.kickstarter_pda),
+            liquidity_pool: to_anchor_pubkey(&self.liquidity_pool),
+            pool_base_vault: to_anchor_pubkey(&self.pool_base_vault),
+            pool_quote_vault: to_anchor_pubkey(&self.pool_quote_vault),
+            lp_mint: to_anchor_pubkey(&self.lp_mint),
+            lp_vault: to_anchor_pubkey(&self.lp_vault),
+            admin_base_account: to_anchor_pubkey(&admin_base_account),
+            admin_quote_account: to_anchor_pubkey(&admin_quote_account),
+            token_program: anchor_spl::token::ID,
+        };
+        Instruction {
+            program_id: program_id(),
+            accounts: convert_metas(accounts.to_account_metas(Some(true))),
+            data: kickstarter::instruction::WithdrawLockedLiquidity {}.data(),
+        }
+    }
+
+    pub fn withdraw_team_budget_ix(&self, admin: &Pubkey, amount: u64) -> Instruction {
+        let accounts = kickstarter::accounts::WithdrawTeamBudget {
+            admin: to_anchor_pubkey(admin),
+            kickstarter: to_anchor_pubkey(&self.kickstarter_pda),
+            treasury_vault: to_anchor_pubkey(&self.treasury_vault),
+            treasury_token_account: to_anchor_pubkey(&self.treasury_token_account),
+            token_program: anchor_spl::token::ID,
+        };
+        Instruction {
+            program_id: program_id(),
+            accounts: convert_metas(accounts.to_account_metas(Some(true))),
+            data: kickstarter::instruction::WithdrawTeamBudget { amount }.data(),
+        }
+    }
+
+    pub fn trigger_refunds_ix(&self, caller: &Pubkey) -> Instruction {
+        let accounts = kickstarter::accounts::TriggerRefunds {
+            caller: to_anchor_pubkey(caller),
+            kickstarter: to_anchor_pubkey(&self.kickstarter_pda),
+            private_state: to_anchor_pubkey(&self.private_state),
+            private_quote_vault: to_anchor_pubkey(&self.private_quote_vault),
+            quote_vault: to_anchor_pubkey(&self.quote_vault),
+            token_program: anchor_spl::token::ID,
+        };
+        Instruction {
+            program_id: program_id(),
+            accounts: convert_metas(accounts.to_account_metas(Some(true))),
+            data: kickstarter::instruction::TriggerRefunds {}.data(),
+        }
+    }
+
+    pub fn configure_performance_package_ix(
+        &self,
+        index: u8,
+        multiplier_bps: u32,
+        allocation: u64,
+        recipient: Pubkey,
+        vesting_duration_seconds: u32,
+        depends_on_previous: bool,
+    ) -> Instruction {
         let accounts = kickstarter::accounts::ConfigurePerformancePackage {
             admin: to_anchor_pubkey(&self.admin.pubkey()),
             kickstarter: to_anchor_pubkey(&self.kickstarter_pda),
+            performance_package: to_anchor_pubkey(&self.performance_package_pda(index)),
+            system_program: system_program::ID,
         };
         Instruction {
             program_id: program_id(),
             accounts: convert_metas(accounts.to_account_metas(Some(true))),
             data: kickstarter::instruction::ConfigurePerformancePackage {
                 index,
-                multiplier,
+                multiplier_bps,
                 allocation,
+                recipient: to_anchor_pubkey(&recipient),
+                vesting_duration_seconds,
+                depends_on_previous,
             }
             .data(),
         }
     }
 
-    pub fn unlock_performance_package_ix(&self, index: u8, current_price: u64) -> Instruction {
+    pub fn performance_package_pda(&self, index: u8) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                constants::SEED_PERFORMANCE_PACKAGE.as_bytes(),
+                self.kickstarter_pda.as_ref(),
+                &[index],
+            ],
+            &program_id(),
+        )
+        .0
+    }
+
+    /// Passes the package at index - 1 along, which packages depending on it need.
+    pub fn unlock_performance_package_ix(&self, index: u8) -> Instruction {
         let accounts = kickstarter::accounts::UnlockPerformancePackage {
             admin: to_anchor_pubkey(&self.admin.pubkey()),
             kickstarter: to_anchor_pubkey(&self.kickstarter_pda),
+            price_observations: to_anchor_pubkey(&self.price_observations),
+            performance_package: to_anchor_pubkey(&self.performance_package_pda(index)),
+            previous_package: index
+                .checked_sub(1)
+                .map(|previous| to_anchor_pubkey(&self.performance_package_pda(previous))),
         };
         Instruction {
             program_id: program_id(),
             accounts: convert_metas(accounts.to_account_metas(Some(true))),
-            data: kickstarter::instruction::UnlockPerformancePackage { index, current_price }.data(),
+            data: kickstarter::instruction::UnlockPerformancePackage { index }.data(),
         }
     }
 
-    pub fn claim_performance_package_ix(&self, index: u8, recipient_base_account: Pubkey) -> Instruction {
+    pub fn record_price_ix(&self, caller: &Pubkey) -> Instruction {
+        let accounts = kickstarter::accounts::RecordPrice {
+            caller: to_anchor_pubkey(caller),
+            kickstarter: to_anchor_pubkey(&self.kickstarter_pda),
+            price_observations: to_anchor_pubkey(&self.price_observations),
+            price_feed: to_anchor_pubkey(&self.price_feed),
+            system_program: system_program::ID,
+        };
+        Instruction {
+            program_id: program_id(),
+            accounts: convert_metas(accounts.to_account_metas(Some(true))),
+            data: kickstarter::instruction::RecordPrice {}.data(),
+        }
+    }
+
+    pub fn claim_performance_package_ix(
+        &self,
+        authority: &Pubkey,
+        index: u8,
+        recipient_base_account: Pubkey,
+    ) -> Instruction {
         let accounts = kickstarter::accounts::ClaimPerformancePackage {
-            admin: to_anchor_pubkey(&self.admin.pubkey()),
+            authority: to_anchor_pubkey(authority),
             kickstarter: to_anchor_pubkey(&self.kickstarter_pda),
+            performance_package: to_anchor_pubkey(&self.performance_package_pda(index)),
             base_vault: to_anchor_pubkey(&self.base_vault),
             recipient_base_account: to_anchor_pubkey(&recipient_base_account),
             token_program: anchor_spl::token::ID,
@@ -457,6 +850,48 @@ impl KickstarterAccounts {
         }
     }
 
+    pub fn release_unconfigured_performance_tokens_ix(
+        &self,
+        release: PerformanceTokenRelease,
+        treasury_base_account: Option<Pubkey>,
+    ) -> Instruction {
+        let accounts = kickstarter::accounts::ReleaseUnconfiguredPerformanceTokens {
+            admin: to_anchor_pubkey(&self.admin.pubkey()),
+            kickstarter: to_anchor_pubkey(&self.kickstarter_pda),
+            base_vault: to_anchor_pubkey(&self.base_vault),
+            base_mint: to_anchor_pubkey(&self.base_mint),
+            treasury_base_account: treasury_base_account.as_ref().map(to_anchor_pubkey),
+            token_program: anchor_spl::token::ID,
+        };
+        Instruction {
+            program_id: program_id(),
+            accounts: convert_metas(accounts.to_account_metas(Some(true))),
+            data: kickstarter::instruction::ReleaseUnconfiguredPerformanceTokens { release }.data(),
+        }
+    }
+
+    pub fn expire_performance_package_ix(
+        &self,
+        index: u8,
+        release: PerformanceTokenRelease,
+        treasury_base_account: Option<Pubkey>,
+    ) -> Instruction {
+        let accounts = kickstarter::accounts::ExpirePerformancePackage {
+            admin: to_anchor_pubkey(&self.admin.pubkey()),
+            kickstarter: to_anchor_pubkey(&self.kickstarter_pda),
+            performance_package: to_anchor_pubkey(&self.performance_package_pda(index)),
+            base_vault: to_anchor_pubkey(&self.base_vault),
+            base_mint: to_anchor_pubkey(&self.base_mint),
+            treasury_base_account: treasury_base_account.as_ref().map(to_anchor_pubkey),
+            token_program: anchor_spl::token::ID,
+        };
+        Instruction {
+            program_id: program_id(),
+            accounts: convert_metas(accounts.to_account_metas(Some(true))),
+            data: kickstarter::instruction::ExpirePerformancePackage { index, release }.data(),
+        }
+    }
+
     pub fn close_ix(&self) -> Instruction {
         let accounts = kickstarter::accounts::CloseKickstarter {
             admin: to_anchor_pubkey(&self.admin.pubkey()),
@@ -481,6 +916,19 @@ impl KickstarterAccounts {
         }
     }
 
+    pub fn set_hard_cap_ix(&self, new_hard_cap: u64, close_on_hard_cap: bool) -> Instruction {
+        let accounts = kickstarter::accounts::SetHardCap {
+            admin: to_anchor_pubkey(&self.admin.pubkey()),
+            kickstarter: to_anchor_pubkey(&self.kickstarter_pda),
+            private_state: to_anchor_pubkey(&self.private_state),
+        };
+        Instruction {
+            program_id: program_id(),
+            accounts: convert_metas(accounts.to_account_metas(Some(true))),
+            data: kickstarter::instruction::SetHardCap { new_hard_cap, close_on_hard_cap }.data(),
+        }
+    }
+
     pub fn stake_from_treasury_ix(&self, staking_account: Pubkey, amount: u64) -> Instruction {
         let accounts = kickstarter::accounts::StakeFromTreasury {
             admin: to_anchor_pubkey(&self.admin.pubkey()),
@@ -505,6 +953,23 @@ pub fn derive_funder_position(kickstarter_pda: &Pubkey, user: &Pubkey) -> Pubkey
     pda
 }
 
+pub fn derive_private_state(kickstarter_pda: &Pubkey) -> Pubkey {
+    let (pda, _) = Pubkey::find_program_address(
+        &[constants::SEED_PRIVATE_STATE.as_bytes(), kickstarter_pda.as_ref()],
+        &program_id(),
+    );
+    pda
+}
+
+pub fn derive_nullifier(kickstarter_pda: &Pubkey, user: &Pubkey, amount: u64, salt: [u8; 32]) -> Pubkey {
+    let nullifier = merkle_tree::nullifier_hash(&commitment_leaf(user, amount, salt));
+    let (pda, _) = Pubkey::find_program_address(
+        &[constants::SEED_NULLIFIER.as_bytes(), kickstarter_pda.as_ref(), nullifier.as_ref()],
+        &program_id(),
+    );
+    pda
+}
+
 pub fn fund_ix(
     user: &Pubkey,
     kickstarter_pda: &Pubkey,
@@ -517,6 +982,7 @@ pub fn fund_ix(
         funder: to_anchor_pubkey(user),
         kickstarter: to_anchor_pubkey(kickstarter_pda),
         funder_position: to_anchor_pubkey(&funder_position),
+        private_state: to_anchor_pubkey(&derive_private_state(kickstarter_pda)),
         funder_quote_account: to_anchor_pubkey(user_quote_account),
         quote_vault: to_anchor_pubkey(quote_vault),
         token_program: anchor_spl::token::ID,
@@ -529,6 +995,106 @@ pub fn fund_ix(
     }
 }
 
+pub fn withdraw_commitment_ix(
+    user: &Pubkey,
+    kickstarter_pda: &Pubkey,
+    user_quote_account: &Pubkey,
+    quote_vault: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let funder_position = derive_funder_position(kickstarter_pda, user);
+    let accounts = kickstarter::accounts::WithdrawCommitment {
+        funder: to_anchor_pubkey(user),
+        kickstarter: to_anchor_pubkey(kickstarter_pda),
+        funder_position: to_anchor_pubkey(&funder_position),
+        funder_quote_account: to_anchor_pubkey(user_quote_account),
+        quote_vault: to_anchor_pubkey(quote_vault),
+        token_program: anchor_spl::token::ID,
+    };
+    Instruction {
+        program_id: program_id(),
+        accounts: convert_metas(accounts.to_account_metas(Some(true))),
+        data: kickstarter::instruction::WithdrawCommitment { amount }.data(),
+    }
+}
+
+/// Leaf that `fund_private` appends for `user` committing `amount` with `salt`.
+pub fn commitment_leaf(user: &Pubkey, amount: u64, salt: [u8; 32]) -> [u8; 32] {
+    merkle_tree::commitment_hash(&to_anchor_pubkey(user), amount, &salt)
+}
+
+/// Builds the inclusion proof for `leaves[index]` off-chain, padding the tree
+/// with empty subtrees the same way `append_leaf` does.
+pub fn derive_private_deposit(kickstarter_pda: &Pubkey, user: &Pubkey) -> Pubkey {
+    let (pda, _) = Pubkey::find_program_address(
+        &[constants::SEED_PRIVATE_DEPOSIT.as_bytes(), kickstarter_pda.as_ref(), user.as_ref()],
+        &program_id(),
+    );
+    pda
+}
+
+pub fn deposit_private_ix(
+    user: &Pubkey,
+    kickstarter_pda: &Pubkey,
+    user_quote_account: &Pubkey,
+    private_quote_vault: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let private_deposit = derive_private_deposit(kickstarter_pda, user);
+    let accounts = kickstarter::accounts::DepositPrivate {
+        funder: to_anchor_pubkey(user),
+        kickstarter: to_anchor_pubkey(kickstarter_pda),
+        private_deposit: to_anchor_pubkey(&private_deposit),
+        funder_quote_account: to_anchor_pubkey(user_quote_account),
+        private_quote_vault: to_anchor_pubkey(private_quote_vault),
+        token_program: anchor_spl::token::ID,
+        system_program: anchor_lang::solana_program::system_program::ID,
+    };
+    Instruction {
+        program_id: program_id(),
+        accounts: convert_metas(accounts.to_account_metas(Some(true))),
+        data: kickstarter::instruction::DepositPrivate { amount }.data(),
+    }
+}
+
+pub fn withdraw_private_deposit_ix(
+    user: &Pubkey,
+    kickstarter_pda: &Pubkey,
+    user_quote_account: &Pubkey,
+    private_quote_vault: &Pubkey,
+) -> Instruction {
+    let private_deposit = derive_private_deposit(kickstarter_pda, user);
+    let accounts = kickstarter::accounts::WithdrawPrivateDeposit {
+        funder: to_anchor_pubkey(user),
+        kickstarter: to_anchor_pubkey(kickstarter_pda),
+        private_deposit: to_anchor_pubkey(&private_deposit),
+        funder_quote_account: to_anchor_pubkey(user_quote_account),
+        private_quote_vault: to_anchor_pubkey(private_quote_vault),
+        token_program: anchor_spl::token::ID,
+    };
+    Instruction {
+        program_id: program_id(),
+        accounts: convert_metas(accounts.to_account_metas(Some(true))),
+        data: kickstarter::instruction::WithdrawPrivateDeposit {}.data(),
+    }
+}
+
+/// Gives `user` a funded quote account and escrows `amount` of it for the private round.
+pub fn deposit_private(
+    harness: &mut TestHarness,
+    accounts: &KickstarterAccounts,
+    user: &Keypair,
+    amount: u64,
+) -> TestResult<Pubkey> {
+    let user_quote = Pubkey::new_unique();
+    harness.set_token_account_balance(user_quote, accounts.quote_mint, user.pubkey(), amount)?;
+    harness.send(
+        deposit_private_ix(&user.pubkey(), &accounts.kickstarter_pda, &user_quote, &accounts.private_quote_vault, amount),
+        user,
+    )?;
+    Ok(user_quote)
+}
+
 pub fn fund_private_ix(
     user: &Pubkey,
     kickstarter_pda: &Pubkey,
@@ -536,10 +1102,12 @@ pub fn fund_private_ix(
     amount: u64,
     salt: [u8; 32],
 ) -> Instruction {
+    let private_deposit = derive_private_deposit(kickstarter_pda, user);
     let accounts = kickstarter::accounts::FundPrivate {
         funder: to_anchor_pubkey(user),
         kickstarter: to_anchor_pubkey(kickstarter_pda),
         private_state: to_anchor_pubkey(private_state),
+        private_deposit: to_anchor_pubkey(&private_deposit),
         magic_context: MAGIC_CONTEXT_ID,
         magic_program: MAGIC_PROGRAM_ID,
     };
@@ -550,20 +1118,70 @@ pub fn fund_private_ix(
     }
 }
 
+/// Ed25519 sigverify instruction with the pubkey, signature and message all
+/// inlined, the layout `verify_ed25519_attestation` expects.
+pub fn ed25519_sigverify_ix(signer: &Keypair, message: &[u8]) -> (Instruction, [u8; 64]) {
+    let signature: [u8; 64] = signer.sign_message(message).into();
+
+    const HEADER_SIZE: u16 = 2 + 14;
+    let pubkey_offset = HEADER_SIZE;
+    let signature_offset = pubkey_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = vec![1u8, 0];
+    for value in [
+        signature_offset,
+        u16::MAX,
+        pubkey_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.pubkey().as_ref());
+    data.extend_from_slice(&signature);
+    data.extend_from_slice(message);
+
+    let ix = Instruction {
+        program_id: Pubkey::new_from_array(kickstarter::attestation::ED25519_PROGRAM_ID.to_bytes()),
+        accounts: vec![],
+        data,
+    };
+    (ix, signature)
+}
+
+/// Returns the attester's sigverify instruction followed by the finalize
+/// instruction, both have to go in the same transaction.
+#[allow(clippy::too_many_arguments)]
 pub fn finalize_private_round_ix(
     admin: &Pubkey,
     kickstarter_pda: &Pubkey,
     private_state: &Pubkey,
+    private_quote_vault: &Pubkey,
+    quote_vault: &Pubkey,
     final_commitments_root: [u8; 32],
     attested_total_amount: u64,
-    attestation_signature: [u8; 64],
-) -> Instruction {
+    attester: &Keypair,
+) -> Vec<Instruction> {
+    let message = kickstarter::attestation::attestation_message(
+        &to_anchor_pubkey(kickstarter_pda),
+        &final_commitments_root,
+        attested_total_amount,
+    );
+    let (sigverify_ix, attestation_signature) = ed25519_sigverify_ix(attester, &message);
+
     let accounts = kickstarter::accounts::FinalizePrivateRound {
         admin: to_anchor_pubkey(admin),
         kickstarter: to_anchor_pubkey(kickstarter_pda),
         private_state: to_anchor_pubkey(private_state),
+        private_quote_vault: to_anchor_pubkey(private_quote_vault),
+        quote_vault: to_anchor_pubkey(quote_vault),
+        token_program: anchor_spl::token::ID,
+        instructions_sysvar: kickstarter::attestation::INSTRUCTIONS_SYSVAR_ID,
     };
-    Instruction {
+    let finalize_ix = Instruction {
         program_id: program_id(),
         accounts: convert_metas(accounts.to_account_metas(Some(true))),
         data: kickstarter::instruction::FinalizePrivateRound {
@@ -571,9 +1189,11 @@ pub fn finalize_private_round_ix(
             attested_total_amount,
             attestation_signature,
         }.data(),
-    }
+    };
+    vec![sigverify_ix, finalize_ix]
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn private_claim_ix(
     user: &Pubkey,
     kickstarter_pda: &Pubkey,
@@ -582,22 +1202,28 @@ pub fn private_claim_ix(
     user_base_account: &Pubkey,
     amount: u64,
     salt: [u8; 32],
+    leaf_index: u32,
+    proof: Vec<[u8; 32]>,
 ) -> Instruction {
+    let nullifier = derive_nullifier(kickstarter_pda, user, amount, salt);
     let accounts = kickstarter::accounts::PrivateClaim {
         user: to_anchor_pubkey(user),
         kickstarter: to_anchor_pubkey(kickstarter_pda),
         private_state: to_anchor_pubkey(private_state),
+        nullifier: to_anchor_pubkey(&nullifier),
         base_vault: to_anchor_pubkey(base_vault),
         user_base_account: to_anchor_pubkey(user_base_account),
         token_program: anchor_spl::token::ID,
+        system_program: anchor_lang::solana_program::system_program::ID,
     };
     Instruction {
         program_id: program_id(),
         accounts: convert_metas(accounts.to_account_metas(Some(true))),
-        data: kickstarter::instruction::PrivateClaim { amount, salt }.data(),
+        data: kickstarter::instruction::PrivateClaim { amount, salt, leaf_index, proof }.data(),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn private_compressed_claim_ix(
     user: &Pubkey,
     kickstarter_pda: &Pubkey,
@@ -606,11 +1232,15 @@ pub fn private_compressed_claim_ix(
     compressed_token_account: &Pubkey,
     amount: u64,
     salt: [u8; 32],
+    leaf_index: u32,
+    proof: Vec<[u8; 32]>,
 ) -> Instruction {
+    let nullifier = derive_nullifier(kickstarter_pda, user, amount, salt);
     let accounts = kickstarter::accounts::PrivateClaimCompressed {
         user: to_anchor_pubkey(user),
         kickstarter: to_anchor_pubkey(kickstarter_pda),
         private_state: to_anchor_pubkey(private_state),
+        nullifier: to_anchor_pubkey(&nullifier),
         base_vault: to_anchor_pubkey(base_vault),
         compressed_token_account: to_anchor_pubkey(compressed_token_account),
         token_program: anchor_spl::token::ID,
@@ -620,10 +1250,11 @@ pub fn private_compressed_claim_ix(
     Instruction {
         program_id: program_id(),
         accounts: convert_metas(accounts.to_account_metas(Some(true))),
-        data: kickstarter::instruction::PrivateClaimCompressed { amount, salt }.data(),
+        data: kickstarter::instruction::PrivateClaimCompressed { amount, salt, leaf_index, proof }.data(),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn private_refund_ix(
     user: &Pubkey,
     kickstarter_pda: &Pubkey,
@@ -632,19 +1263,24 @@ pub fn private_refund_ix(
     user_quote_account: &Pubkey,
     amount: u64,
     salt: [u8; 32],
+    leaf_index: u32,
+    proof: Vec<[u8; 32]>,
 ) -> Instruction {
+    let nullifier = derive_nullifier(kickstarter_pda, user, amount, salt);
     let accounts = kickstarter::accounts::PrivateRefund {
         user: to_anchor_pubkey(user),
         kickstarter: to_anchor_pubkey(kickstarter_pda),
         private_state: to_anchor_pubkey(private_state),
+        nullifier: to_anchor_pubkey(&nullifier),
         quote_vault: to_anchor_pubkey(quote_vault),
         user_quote_account: to_anchor_pubkey(user_quote_account),
         token_program: anchor_spl::token::ID,
+        system_program: anchor_lang::solana_program::system_program::ID,
     };
     Instruction {
         program_id: program_id(),
         accounts: convert_metas(accounts.to_account_metas(Some(true))),
-        data: kickstarter::instruction::PrivateRefund { amount, salt }.data(),
+        data: kickstarter::instruction::PrivateRefund { amount, salt, leaf_index, proof }.data(),
     }
 }
 
@@ -727,6 +1363,19 @@ pub struct InitConfig {
     pub token_symbol: String,
     pub token_description: String,
     pub token_image_url: String,
+    pub hard_cap: u64,
+    pub close_on_hard_cap: bool,
+    pub withdrawal_cutoff_seconds: u32,
+    pub vesting_tge_bps: u16,
+    pub vesting_cliff_seconds: u32,
+    pub vesting_duration_seconds: u32,
+    pub max_price_age_seconds: u32,
+    pub max_price_confidence_bps: u16,
+    pub twap_window_seconds: u32,
+    pub performance_package_expiry_seconds: u32,
+    pub liquidity_lock_seconds: u32,
+    pub liquidity_bps: u16,
+    pub base_tokens_for_liquidity_bps: u16,
 }
 
 impl Default for InitConfig {
@@ -742,6 +1391,19 @@ impl Default for InitConfig {
             token_symbol: "TEST".to_string(),
             token_description: "Description".to_string(),
             token_image_url: "https://example.com/image.png".to_string(),
+            hard_cap: u64::MAX,
+            close_on_hard_cap: false,
+            withdrawal_cutoff_seconds: 600,
+            vesting_tge_bps: 10_000,
+            vesting_cliff_seconds: 0,
+            vesting_duration_seconds: 0,
+            max_price_age_seconds: 60,
+            max_price_confidence_bps: 100,
+            twap_window_seconds: 3_200,
+            performance_package_expiry_seconds: 0,
+            liquidity_lock_seconds: 86_400,
+            liquidity_bps: 2_000,
+            base_tokens_for_liquidity_bps: 2_900,
         }
     }
 }
@@ -0,0 +1,109 @@
+mod support;
+
+use kickstarter::{constants::TEAM_BUDGET_PERIOD_SECONDS, error::ErrorCode};
+use solana_keypair::Keypair;
+use support::{
+    assert_error_code, to_anchor_pubkey, InitConfig, KickstarterAccounts, Signer, TestHarness,
+    TestResult,
+};
+
+const FINAL_RAISE_AMOUNT: u64 = 1_000_000;
+const MONTHLY_SPENDING: u64 = 100_000;
+
+fn complete_raise(harness: &mut TestHarness, accounts: &KickstarterAccounts) -> TestResult<i64> {
+    let config = InitConfig {
+        minimum_raise_amount: 500_000,
+        monthly_team_spending_usdc: MONTHLY_SPENDING,
+        ..Default::default()
+    };
+    harness.complete_raise(accounts, config, vec![], FINAL_RAISE_AMOUNT)
+}
+
+#[test]
+fn complete_keeps_post_liquidity_funds_in_treasury_vault() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let completed_at = complete_raise(&mut harness, &accounts)?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    let liquidity = decoded.calculated_liquidity_amount.unwrap();
+    assert_eq!(decoded.treasury_vault, to_anchor_pubkey(&accounts.treasury_vault));
+    assert_eq!(harness.token_balance(&accounts.treasury_vault)?, FINAL_RAISE_AMOUNT - liquidity);
+    assert_eq!(harness.token_balance(&accounts.treasury_token_account)?, 0);
+    assert_eq!(decoded.team_budget_accrued_until, Some(completed_at));
+    assert_eq!(decoded.team_budget_available, 0);
+
+    Ok(())
+}
+
+#[test]
+fn team_budget_accrues_per_elapsed_period() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    let completed_at = complete_raise(&mut harness, &accounts)?;
+    let vault_balance = harness.token_balance(&accounts.treasury_vault)?;
+
+    // nothing before the first period has elapsed
+    let result = harness.send(
+        accounts.withdraw_team_budget_ix(&accounts.admin.pubkey(), 1),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::TeamBudgetExceeded)?;
+
+    harness.warp_to_timestamp(completed_at + TEAM_BUDGET_PERIOD_SECONDS);
+    let result = harness.send(
+        accounts.withdraw_team_budget_ix(&accounts.admin.pubkey(), MONTHLY_SPENDING + 1),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::TeamBudgetExceeded)?;
+
+    harness.send(
+        accounts.withdraw_team_budget_ix(&accounts.admin.pubkey(), MONTHLY_SPENDING),
+        &accounts.admin,
+    )?;
+    assert_eq!(harness.token_balance(&accounts.treasury_token_account)?, MONTHLY_SPENDING);
+
+    // two more full periods and part of a fourth
+    harness.warp_to_timestamp(completed_at + 3 * TEAM_BUDGET_PERIOD_SECONDS + 1_000);
+    let result = harness.send(
+        accounts.withdraw_team_budget_ix(&accounts.admin.pubkey(), 2 * MONTHLY_SPENDING + 1),
+        &accounts.admin,
+    );
+    assert_error_code(result, ErrorCode::TeamBudgetExceeded)?;
+
+    harness.send(
+        accounts.withdraw_team_budget_ix(&accounts.admin.pubkey(), 2 * MONTHLY_SPENDING),
+        &accounts.admin,
+    )?;
+
+    let decoded = harness.kickstarter_state(&accounts.kickstarter_pda)?;
+    assert_eq!(decoded.team_budget_available, 0);
+    assert_eq!(
+        decoded.team_budget_accrued_until,
+        Some(completed_at + 3 * TEAM_BUDGET_PERIOD_SECONDS)
+    );
+    assert_eq!(harness.token_balance(&accounts.treasury_token_account)?, 3 * MONTHLY_SPENDING);
+    assert_eq!(
+        harness.token_balance(&accounts.treasury_vault)?,
+        vault_balance - 3 * MONTHLY_SPENDING
+    );
+
+    Ok(())
+}
+
+#[test]
+fn withdraw_team_budget_requires_admin() -> TestResult {
+    let mut harness = TestHarness::new()?;
+    let accounts = KickstarterAccounts::generate(&mut harness)?;
+    complete_raise(&mut harness, &accounts)?;
+
+    let outsider = Keypair::new();
+    harness.airdrop(&outsider.pubkey(), 1_000_000_000)?;
+    let result = harness.send(
+        accounts.withdraw_team_budget_ix(&outsider.pubkey(), MONTHLY_SPENDING),
+        &outsider,
+    );
+    assert_error_code(result, ErrorCode::InvalidAdmin)?;
+
+    Ok(())
+}
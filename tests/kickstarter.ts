@@ -21,7 +21,9 @@ import {
   SystemProgram,
   PublicKey,
   Keypair,
+  LAMPORTS_PER_SOL,
   SYSVAR_RENT_PUBKEY,
+  Transaction,
 } from "@solana/web3.js";
 import { sign } from "tweetnacl";
 import * as fs from "fs";
@@ -35,6 +37,8 @@ const SEED_KICKSTARTER = 'kickstarter';
 const SEED_BASE_VAULT = 'base_vault';
 const SEED_QUOTE_VAULT = 'quote_vault';
 const SEED_FUNDER_POSITION = 'funder_position';
+const SEED_PRIVATE_DEPOSIT = 'private_deposit';
+const SEED_PRIVATE_QUOTE_VAULT = 'private_quote_vault';
 
 // HELPERS
 const addLog = (msg: string) => console.log(`[${new Date().toLocaleTimeString()}] ${msg}`);
@@ -79,11 +83,14 @@ describe("kickstarter", () => {
   // My program accounts
   let kickstarterPda: PublicKey;
   let privateState: PublicKey;
+  let privateDeposit: PublicKey;
 
   let baseMint: PublicKey;
   let quoteMint: PublicKey;
   let baseVaultPda: PublicKey;
   let quoteVaultPda: PublicKey;
+  let privateQuoteVaultPda: PublicKey;
+  let investorQuoteAta: PublicKey;
 
   const privateFundAmount = '100'
   const amount = parseFloat(privateFundAmount) * 1_000_000;
@@ -152,6 +159,12 @@ describe("kickstarter", () => {
     );
     addLog(`Private State PDA: ${privateState.toBase58()}`);
 
+    [privateDeposit] = PublicKey.findProgramAddressSync(
+      [Buffer.from(SEED_PRIVATE_DEPOSIT), kickstarterPda.toBuffer(), investor.toBuffer()],
+      solanaProgram.programId
+    );
+    addLog(`Private Deposit PDA: ${privateDeposit.toBase58()}`);
+
     [baseVaultPda] = PublicKey.findProgramAddressSync(
       [Buffer.from(SEED_BASE_VAULT), kickstarterPda.toBuffer()],
       solanaProgram.programId
@@ -160,6 +173,10 @@ describe("kickstarter", () => {
       [Buffer.from(SEED_QUOTE_VAULT), kickstarterPda.toBuffer()],
       solanaProgram.programId
     );
+    [privateQuoteVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(SEED_PRIVATE_QUOTE_VAULT), kickstarterPda.toBuffer()],
+      solanaProgram.programId
+    );
     treasury = admin;
 
     [metadataPda] = PublicKey.findProgramAddressSync(
@@ -176,10 +193,11 @@ describe("kickstarter", () => {
 
     console.log("Creating user token accounts...");
     const investorAta = await getOrCreateAssociatedTokenAccount(solanaProvider.connection, adminKeypair, quoteMint, investor);
-    await mintTo(solanaProvider.connection, adminKeypair, quoteMint, investorAta.address, adminKeypair, amount * 2);
+    investorQuoteAta = investorAta.address;
+    await mintTo(solanaProvider.connection, adminKeypair, quoteMint, investorQuoteAta, adminKeypair, amount * 2);
 
     while (
-      (await solanaProvider.connection.getAccountInfo(investorAta.address)) === null
+      (await solanaProvider.connection.getAccountInfo(investorQuoteAta)) === null
     ) {
       console.log(`Waiting for investor ata to be created...`);
       await new Promise((resolve) => setTimeout(resolve, 1000));
@@ -211,7 +229,23 @@ describe("kickstarter", () => {
           initParams.tokenName,
           initParams.tokenSymbol,
           "Description",
-          initParams.tokenUri
+          initParams.tokenUri,
+          adminKeypair.publicKey,
+          new BN("18446744073709551615"),
+          false,
+          600,
+          10_000,
+          0,
+          0,
+          PublicKey.default,
+          Array(32).fill(0),
+          60,
+          100,
+          3_200,
+          0,
+          86_400,
+          2_000,
+          2_900
         )
         .accounts({
           admin: adminKeypair.publicKey,
@@ -255,6 +289,34 @@ describe("kickstarter", () => {
     addLog(`PRIVATE ROUND STARTED! Tx: ${tx}`);
   });
 
+  it("Deposit private", async () => {
+    // quote tokens are escrowed on the base layer, fund_private only spends from the escrow;
+    // the investor pays rent for the private deposit PDA
+    if ((await solanaProvider.connection.getBalance(investor)) === 0) {
+      await solanaProvider.sendAndConfirm(new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: admin, toPubkey: investor, lamports: LAMPORTS_PER_SOL / 100 })
+      ));
+    }
+
+    addLog(`Depositing ${privateFundAmount} USDC from Investor ${investor}...`);
+    const tx = await solanaProgram.methods
+      .depositPrivate(new BN(amount))
+      .accounts({
+        funder: investor,
+        kickstarter: kickstarterPda,
+        // @ts-ignore
+        privateDeposit,
+        funderQuoteAccount: investorQuoteAta,
+        privateQuoteVault: privateQuoteVaultPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([investorKeypair])
+      .rpc();
+
+    addLog(`PRIVATE DEPOSIT SUCCESS! Tx: ${tx}`);
+  });
+
   it('Create permission for investor', async () => {
       const PERMISSION_FLAGS =
         MagicBlockSdk.AUTHORITY_FLAG |
@@ -266,51 +328,64 @@ describe("kickstarter", () => {
         { pubkey: investor, flags: PERMISSION_FLAGS },
         { pubkey: adminKeypair.publicKey, flags: PERMISSION_FLAGS },
       ];
-      const privateStatePermission = MagicBlockSdk.permissionPdaFromAccount(privateState);
+      const permissioned = [
+        { accountType: { privateState: { kickstarter: kickstarterPda } }, account: privateState },
+        { accountType: { privateDeposit: { kickstarter: kickstarterPda, owner: investor } }, account: privateDeposit },
+      ];
 
-      const tx = await solanaProgram.methods
-          .createPermission({ privateState: { kickstarter: kickstarterPda } }, members)
-          .accounts({
-            permissionedAccount: privateState,
-            permission: privateStatePermission,
-            payer: adminKeypair.publicKey,
-            // @ts-ignore
-            permissionProgram: MagicBlockSdk.PERMISSION_PROGRAM_ID,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([adminKeypair])
-          .rpc();
-    addLog(`Permission created! Tx: ${tx}`);
-    const permissionStatus = await MagicBlockSdk.waitUntilPermissionActive(ephemeralProgramInvestor.provider.connection.rpcEndpoint, privateState);
-    addLog(`is permission active: ${permissionStatus}`);
+      for (const { accountType, account } of permissioned) {
+        const tx = await solanaProgram.methods
+            .createPermission(accountType, members)
+            .accounts({
+              permissionedAccount: account,
+              permission: MagicBlockSdk.permissionPdaFromAccount(account),
+              payer: adminKeypair.publicKey,
+              // @ts-ignore
+              permissionProgram: MagicBlockSdk.PERMISSION_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([adminKeypair])
+            .rpc();
+        addLog(`Permission created for ${account.toBase58()}! Tx: ${tx}`);
+        const permissionStatus = await MagicBlockSdk.waitUntilPermissionActive(ephemeralProgramInvestor.provider.connection.rpcEndpoint, account);
+        addLog(`is permission active: ${permissionStatus}`);
+      }
   })
 
   it("Delegate", async () => {
     const validator = new PublicKey("FnE6VJT5QNZdedZPnCoLsARgBwoE6DeJNjBs2H1gySXA");
-    const delegateAccounts = {
-      bufferPda: MagicBlockSdk.delegateBufferPdaFromDelegatedAccountAndOwnerProgram(
-        privateState,
-        solanaProgram.programId
-      ),
-      delegationRecordPda: MagicBlockSdk.delegationRecordPdaFromDelegatedAccount(privateState),
-      delegationMetadataPda: MagicBlockSdk.delegationMetadataPdaFromDelegatedAccount(privateState),
-      pda: privateState,
-      payer: adminKeypair.publicKey,
-      ownerProgram: solanaProgram.programId,
-      delegationProgram: MagicBlockSdk.DELEGATION_PROGRAM_ID,
-      systemProgram: SystemProgram.programId,
-      validator,
-    };
-    const tx = await solanaProgram.methods
-      .delegatePda({ privateState: { kickstarter: kickstarterPda } })
-      .accountsPartial(delegateAccounts)
-      .signers([adminKeypair])
-      .rpc();
-    addLog(`Delegated! Tx: ${tx}`);
     const router = new MagicBlockSdk.ConnectionMagicRouter(ephemeralRpcUrl);
-    while (!(await router.getDelegationStatus(privateState)).isDelegated) {
-      await new Promise((resolve) => setTimeout(resolve, 1000));
-      console.log("Waiting for delegation to be active...");
+    // fund_private writes both the commitment tree and the investor's escrow on the rollup
+    const delegated = [
+      { accountType: { privateState: { kickstarter: kickstarterPda } }, account: privateState },
+      { accountType: { privateDeposit: { kickstarter: kickstarterPda, owner: investor } }, account: privateDeposit },
+    ];
+
+    for (const { accountType, account } of delegated) {
+      const delegateAccounts = {
+        bufferPda: MagicBlockSdk.delegateBufferPdaFromDelegatedAccountAndOwnerProgram(
+          account,
+          solanaProgram.programId
+        ),
+        delegationRecordPda: MagicBlockSdk.delegationRecordPdaFromDelegatedAccount(account),
+        delegationMetadataPda: MagicBlockSdk.delegationMetadataPdaFromDelegatedAccount(account),
+        pda: account,
+        payer: adminKeypair.publicKey,
+        ownerProgram: solanaProgram.programId,
+        delegationProgram: MagicBlockSdk.DELEGATION_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        validator,
+      };
+      const tx = await solanaProgram.methods
+        .delegatePda(accountType)
+        .accountsPartial(delegateAccounts)
+        .signers([adminKeypair])
+        .rpc();
+      addLog(`Delegated ${account.toBase58()}! Tx: ${tx}`);
+      while (!(await router.getDelegationStatus(account)).isDelegated) {
+        await new Promise((resolve) => setTimeout(resolve, 1000));
+        console.log("Waiting for delegation to be active...");
+      }
     }
     addLog(`Delegation is active`);
 
@@ -357,6 +432,7 @@ describe("kickstarter", () => {
         // @ts-ignore
         kickstarter: kickstarterPda,
         privateState,
+        privateDeposit,
         magicContext: MAGIC_CONTEXT_ID,
         magicProgram: MagicBlockSdk.MAGIC_PROGRAM_ID,
       })